@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Quota-scoped operations subject to permission checks
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+    UseQuota,
+    TransferQuota,
+    CancelTransfer,
+    ReviewTransfer,
+    AcceptTransfer,
+    UpdateQuota,
+    SuspendQuota,
+    ReactivateQuota,
+    RevokeQuota,
+    CloseQuota,
+    ManageClearance,
+    AssignQualityPolicy,
+    MigrateAccount,
+    FlagShipment,
+    AttachAssay,
+    UpdateShipment,
+    DispatchShipment,
+    ConfirmDelivery,
+    RejectShipment,
+    CancelShipment,
+    GrantRole(Role),
+    ManageDelegates,
+    ReserveQuota,
+    ReleaseReservation,
+    VerifyWagon,
+}
+
+impl Role {
+    /// Check if the role allows the action
+    pub fn permits(&self, action: Action) -> bool {
+        match self {
+            Role::DispatchClerk => matches!(
+                action,
+                Action::UseQuota
+                    | Action::ReserveQuota
+                    | Action::ReleaseReservation
+                    | Action::AttachAssay
+                    | Action::DispatchShipment
+                    | Action::ConfirmDelivery
+                    | Action::CancelShipment
+            ),
+            Role::Inspector => matches!(
+                action,
+                Action::FlagShipment
+                    | Action::AttachAssay
+                    | Action::VerifyWagon
+                    | Action::ConfirmDelivery
+                    | Action::RejectShipment
+            ),
+            Role::Auditor => matches!(action, Action::FlagShipment),
+        }
+    }
+}
+
+/// Check if the concession holder may perform the action
+fn holder_permits(action: Action) -> bool {
+    matches!(
+        action,
+        Action::UseQuota
+            | Action::TransferQuota
+            | Action::CancelTransfer
+            | Action::AcceptTransfer
+            | Action::AttachAssay
+            | Action::UpdateShipment
+            | Action::DispatchShipment
+            | Action::ConfirmDelivery
+            | Action::CancelShipment
+            | Action::GrantRole(Role::DispatchClerk)
+            | Action::ManageDelegates
+            | Action::ReserveQuota
+            | Action::ReleaseReservation
+    )
+}
+
+/// Check if the issuing regulator may perform the action
+fn regulator_permits(action: Action) -> bool {
+    matches!(
+        action,
+        Action::ReviewTransfer
+            | Action::CancelTransfer
+            | Action::UpdateQuota
+            | Action::SuspendQuota
+            | Action::ReactivateQuota
+            | Action::RevokeQuota
+            | Action::CloseQuota
+            | Action::ManageClearance
+            | Action::AssignQualityPolicy
+            | Action::MigrateAccount
+            | Action::FlagShipment
+            | Action::AttachAssay
+            | Action::VerifyWagon
+            | Action::ConfirmDelivery
+            | Action::RejectShipment
+            | Action::ReleaseReservation
+            | Action::GrantRole(_)
+    )
+}
+
+/// Check if a dispatch delegate may perform the action
+fn delegate_permits(action: Action) -> bool {
+    matches!(
+        action,
+        Action::UseQuota
+            | Action::UpdateShipment
+            | Action::DispatchShipment
+            | Action::CancelShipment
+    )
+}
+
+/// Revocation is terminal: a revoked quota may only be migrated, have the
+/// shipments already on the road settled, have its holds released and its
+/// transfers still in flight cancelled, and be closed
+fn ensure_not_revoked(quota_account: &QuotaAccount, action: Action) -> Result<()> {
+    require!(
+        quota_account.status != QuotaStatus::Revoked
+            || matches!(
+                action,
+                Action::MigrateAccount
+                    | Action::ConfirmDelivery
+                    | Action::RejectShipment
+                    | Action::ReleaseReservation
+                    | Action::CancelTransfer
+                    | Action::CloseQuota
+            ),
+        QuotaError::QuotaRevoked
+    );
+    Ok(())
+}
+
+/// Ensure `signer` may perform `action` on the quota at `quota_key`, either as
+/// its holder, its regulator or through a role assignment on that quota
+pub fn authorize(
+    quota_account: &QuotaAccount,
+    quota_key: Pubkey,
+    signer: Pubkey,
+    role_assignment: Option<&RoleAssignment>,
+    action: Action,
+) -> Result<()> {
+    ensure_not_revoked(quota_account, action)?;
+
+    if signer == quota_account.holder && holder_permits(action) {
+        return Ok(());
+    }
+    if signer == quota_account.regulator && regulator_permits(action) {
+        return Ok(());
+    }
+    if let Some(assignment) = role_assignment {
+        if assignment.quota_account == quota_key
+            && assignment.member == signer
+            && assignment.role.permits(action)
+        {
+            return Ok(());
+        }
+    }
+
+    err!(QuotaError::MissingPermission)
+}
+
+/// Like `authorize`, but also accepts an unexpired delegate of the quota.
+/// Callers must bind `delegate` to the quota and signer through its PDA seeds.
+pub fn authorize_with_delegate(
+    quota_account: &QuotaAccount,
+    quota_key: Pubkey,
+    signer: Pubkey,
+    role_assignment: Option<&RoleAssignment>,
+    delegate: Option<&Delegate>,
+    now: i64,
+    action: Action,
+) -> Result<()> {
+    if let Some(delegate) = delegate {
+        ensure_not_revoked(quota_account, action)?;
+        require!(delegate_permits(action), QuotaError::MissingPermission);
+        require!(delegate.is_active(now), QuotaError::DelegateExpired);
+        return Ok(());
+    }
+
+    authorize(quota_account, quota_key, signer, role_assignment, action)
+}
@@ -71,21 +71,219 @@ pub enum QuotaError {
     #[msg("Transfer amount exceeds available quota")]
     TransferAmountExceedsAvailable,
     
-    #[msg("Quota utilization threshold exceeded")]
+    #[msg("Dispatch would exceed the quota's utilization cap")]
     UtilizationThresholdExceeded,
     
     #[msg("Duplicate shipment ID")]
     DuplicateShipmentId,
     
-    #[msg("Invalid GCV value - must be between 2000-8000 kcal/kg")]
+    #[msg("GCV outside the quality policy's range")]
     InvalidGCVValue,
     
-    #[msg("Invalid moisture content - must be between 0-50%")]
+    #[msg("Moisture content above the quality policy's limit")]
     InvalidMoistureContent,
     
-    #[msg("Invalid ash content - must be between 0-50%")]
+    #[msg("Ash content above the quality policy's limit")]
     InvalidAshContent,
     
-    #[msg("Invalid sulphur content - must be between 0-10%")]
+    #[msg("Sulphur content above the quality policy's limit")]
     InvalidSulphurContent,
-}
\ No newline at end of file
+    
+    #[msg("Account data does not match any known layout")]
+    UnrecognizedAccountLayout,
+    
+    #[msg("Account is already in the current layout")]
+    AccountAlreadyMigrated,
+    
+    #[msg("Account must be migrated to the current layout first")]
+    AccountMigrationRequired,
+    
+    #[msg("Unauthorized - only the program admin can perform this action")]
+    UnauthorizedAdmin,
+    
+    #[msg("Regulator is not registered as active")]
+    RegulatorNotActive,
+    
+    #[msg("Regulator is not disabled")]
+    RegulatorNotDisabled,
+    
+    #[msg("Regulator name too long - maximum 64 characters")]
+    RegulatorNameTooLong,
+    
+    #[msg("Jurisdiction too long - maximum 64 characters")]
+    JurisdictionTooLong,
+    
+    #[msg("Signer lacks permission for this action on the quota")]
+    MissingPermission,
+    
+    #[msg("Shipment does not belong to this quota")]
+    ShipmentQuotaMismatch,
+    
+    #[msg("Invalid delegate limits - daily cap must be between 1 and the total allowance")]
+    InvalidDelegateLimits,
+    
+    #[msg("Delegate authorisation has expired")]
+    DelegateExpired,
+    
+    #[msg("Amount exceeds the delegate's remaining allowance")]
+    DelegateAllowanceExceeded,
+    
+    #[msg("Amount exceeds the delegate's daily cap")]
+    DelegateDailyCapExceeded,
+    
+    #[msg("Transfer is not in the required stage for this action")]
+    InvalidTransferStatus,
+    
+    #[msg("Transfer record does not belong to this quota")]
+    TransferQuotaMismatch,
+    
+    #[msg("Shipment details can no longer be edited")]
+    ShipmentEditWindowClosed,
+    
+    #[msg("Shipment cannot move to the requested stage from its current stage")]
+    InvalidShipmentTransition,
+    
+    #[msg("Shipment has already been delivered, cancelled or rejected")]
+    ShipmentAlreadyFinalised,
+    
+    #[msg("Cancellation window must not be negative")]
+    InvalidCancellationWindow,
+    
+    #[msg("Shipment can no longer be cancelled")]
+    CancellationWindowClosed,
+    
+    #[msg("Quota validity period has not elapsed yet")]
+    ValidityPeriodNotElapsed,
+    
+    #[msg("Quota is already expired or revoked")]
+    QuotaAlreadyClosed,
+    
+    #[msg("Quota has been revoked")]
+    QuotaRevoked,
+    
+    #[msg("Quotas can only be revoked through revoke_quota")]
+    RevocationRequiresReasonCode,
+    
+    #[msg("Quota must be expired or revoked with no open shipments, holds or transfers to be closed")]
+    QuotaNotClosable,
+    
+    #[msg("Record cannot be closed until it is settled and its retention period has passed")]
+    RecordNotClosable,
+    
+    #[msg("Rent must be returned to the account's original payer")]
+    PayerMismatch,
+    
+    #[msg("Issuing authority name too long")]
+    IssuingAuthorityTooLong,
+    
+    #[msg("Clearance needs a positive capacity and a validity window that has not already ended")]
+    InvalidClearanceParameters,
+    
+    #[msg("Environmental clearance is suspended")]
+    ClearanceSuspended,
+    
+    #[msg("Environmental clearance is not valid at this time")]
+    ClearanceExpired,
+    
+    #[msg("Dispatch would exceed the clearance's annual capacity")]
+    ClearanceCapacityExceeded,
+    
+    #[msg("Volatile matter outside the quality policy's range")]
+    InvalidVolatileMatter,
+    
+    #[msg("Fixed carbon outside the quality policy's range")]
+    InvalidFixedCarbon,
+    
+    #[msg("Policy ID too long")]
+    PolicyIdTooLong,
+    
+    #[msg("Quality policy is not the one assigned to the quota")]
+    QualityPolicyMismatch,
+    
+    #[msg("No tonnage is sanctioned for this coal grade")]
+    GradeNotAllocated,
+    
+    #[msg("Insufficient quota remaining for this coal grade")]
+    InsufficientGradeQuota,
+    
+    #[msg("Grade allocations would exceed the quota's allocation")]
+    GradeAllocationExceedsQuota,
+    
+    #[msg("Lab name too long")]
+    LabNameTooLong,
+    
+    #[msg("Accreditation ID too long")]
+    AccreditationIdTooLong,
+    
+    #[msg("Lab is not accredited to sign assays")]
+    LabNotActive,
+    
+    #[msg("Sample ID too long")]
+    SampleIdTooLong,
+    
+    #[msg("An assay is already attached to this shipment")]
+    AssayAlreadyAttached,
+    
+    #[msg("Assay must be signed by the lab in a preceding Ed25519 instruction")]
+    InvalidAssaySignature,
+    
+    #[msg("Warning thresholds must be ascending percentages and the cap below 100%")]
+    InvalidUtilizationThresholds,
+    
+    #[msg("Dispatch exceeds the concession's daily or weekly rate limit")]
+    DispatchRateLimitExceeded,
+    
+    #[msg("Dispatch limits must be greater than zero")]
+    InvalidDispatchLimits,
+    
+    #[msg("Reservation reference too long")]
+    ReservationReferenceTooLong,
+    
+    #[msg("Reservation must expire in the future, within the maximum hold period and the quota's validity")]
+    InvalidReservationExpiry,
+    
+    #[msg("Reservation has expired")]
+    ReservationExpired,
+    
+    #[msg("Reservation does not belong to this quota")]
+    ReservationQuotaMismatch,
+    
+    #[msg("Batch must contain between 1 and 16 shipments")]
+    InvalidBatchSize,
+    
+    #[msg("Remaining accounts must be the usage record PDAs for each shipment in order")]
+    BatchAccountsMismatch,
+    
+    #[msg("Siding code too long")]
+    SidingCodeTooLong,
+    
+    #[msg("Rake must have between 1 and 128 wagons and a positive net weight")]
+    InvalidRakeParameters,
+    
+    #[msg("Wagon index is outside the rake")]
+    InvalidWagonIndex,
+    
+    #[msg("Wagon has already been verified")]
+    WagonAlreadyVerified,
+    
+    #[msg("Wagon weights do not match the rake's Merkle root")]
+    InvalidWagonProof,
+    
+    #[msg("Delegate account does not match the one the shipment was charged to")]
+    DelegateMismatch,
+    
+    #[msg("Quota must be revoked or closed before its accounts can be closed")]
+    QuotaNotRetired,
+    
+    #[msg("Clearance does not belong to this quota")]
+    ClearanceQuotaMismatch,
+    
+    #[msg("Role assignment does not belong to this quota")]
+    RoleQuotaMismatch,
+    
+    #[msg("Delegate does not belong to this quota")]
+    DelegateQuotaMismatch,
+    
+    #[msg("Shipment has been cancelled or rejected")]
+    ShipmentWithdrawn,
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct AcceptTransfer<'info> {
+    #[account(
+        seeds = [b"quota", to_quota.concession_id.as_bytes(), to_quota.holder.as_ref()],
+        bump = to_quota.bump,
+        constraint = QuotaAccount::is_current_layout(to_quota.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub to_quota: Account<'info, QuotaAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"transfer",
+            transfer_record.from_quota.as_ref(),
+            &transfer_record.sequence.to_le_bytes()
+        ],
+        bump = transfer_record.bump,
+        has_one = to_quota @ QuotaError::TransferQuotaMismatch
+    )]
+    pub transfer_record: Account<'info, TransferRecord>,
+
+    /// Holder of the receiving quota
+    pub holder: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptTransfer>) -> Result<()> {
+    authorize(
+        &ctx.accounts.to_quota,
+        ctx.accounts.to_quota.key(),
+        ctx.accounts.holder.key(),
+        None,
+        Action::AcceptTransfer,
+    )?;
+
+    let transfer_record = &mut ctx.accounts.transfer_record;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Recipient consent comes after regulator approval
+    require!(
+        transfer_record.status == TransferStatus::Approved,
+        QuotaError::InvalidTransferStatus
+    );
+
+    let old_status = transfer_record.status.clone();
+    transfer_record.status = TransferStatus::Accepted;
+    transfer_record.accepted_at = current_time;
+
+    // Emit event
+    emit!(TransferStatusUpdated {
+        from_concession: transfer_record.from_concession.clone(),
+        to_concession: transfer_record.to_concession.clone(),
+        old_status,
+        new_status: TransferStatus::Accepted,
+        updated_by: ctx.accounts.holder.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
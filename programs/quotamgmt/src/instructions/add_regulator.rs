@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct AddRegulator<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = program_config.bump,
+        has_one = admin @ QuotaError::UnauthorizedAdmin
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = RegulatorRecord::LEN,
+        seeds = [b"regulator", regulator.key().as_ref()],
+        bump
+    )]
+    pub regulator_record: Account<'info, RegulatorRecord>,
+
+    /// CHECK: Key being registered as a regulator
+    pub regulator: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddRegulator>, name: String, jurisdiction: String) -> Result<()> {
+    // Validation
+    require!(
+        name.len() <= RegulatorRecord::MAX_NAME_LEN,
+        QuotaError::RegulatorNameTooLong
+    );
+    require!(
+        jurisdiction.len() <= RegulatorRecord::MAX_JURISDICTION_LEN,
+        QuotaError::JurisdictionTooLong
+    );
+
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let regulator_record = &mut ctx.accounts.regulator_record;
+    regulator_record.regulator = ctx.accounts.regulator.key();
+    regulator_record.name = name.clone();
+    regulator_record.jurisdiction = jurisdiction.clone();
+    regulator_record.status = RegulatorStatus::Active;
+    regulator_record.added_by = ctx.accounts.admin.key();
+    regulator_record.added_at = current_time;
+    regulator_record.updated_at = current_time;
+    regulator_record.bump = ctx.bumps.regulator_record;
+
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.regulator_count += 1;
+    program_config.updated_at = current_time;
+
+    // Emit event
+    emit!(RegulatorAdded {
+        regulator: regulator_record.regulator,
+        name,
+        jurisdiction,
+        added_by: regulator_record.added_by,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
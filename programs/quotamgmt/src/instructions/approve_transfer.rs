@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ApproveTransfer<'info> {
+    #[account(
+        seeds = [b"quota", from_quota.concession_id.as_bytes(), from_quota.holder.as_ref()],
+        bump = from_quota.bump,
+        constraint = QuotaAccount::is_current_layout(from_quota.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub from_quota: Account<'info, QuotaAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"transfer",
+            transfer_record.from_quota.as_ref(),
+            &transfer_record.sequence.to_le_bytes()
+        ],
+        bump = transfer_record.bump,
+        has_one = from_quota @ QuotaError::TransferQuotaMismatch
+    )]
+    pub transfer_record: Account<'info, TransferRecord>,
+
+    pub regulator: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ApproveTransfer>) -> Result<()> {
+    authorize(
+        &ctx.accounts.from_quota,
+        ctx.accounts.from_quota.key(),
+        ctx.accounts.regulator.key(),
+        None,
+        Action::ReviewTransfer,
+    )?;
+
+    let transfer_record = &mut ctx.accounts.transfer_record;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Can only approve pending requests
+    require!(
+        transfer_record.status == TransferStatus::Requested,
+        QuotaError::InvalidTransferStatus
+    );
+
+    let old_status = transfer_record.status.clone();
+    transfer_record.status = TransferStatus::Approved;
+    transfer_record.authorized_by = ctx.accounts.regulator.key();
+    transfer_record.decided_at = current_time;
+
+    // Emit event
+    emit!(TransferStatusUpdated {
+        from_concession: transfer_record.from_concession.clone(),
+        to_concession: transfer_record.to_concession.clone(),
+        old_status,
+        new_status: TransferStatus::Approved,
+        updated_by: ctx.accounts.regulator.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
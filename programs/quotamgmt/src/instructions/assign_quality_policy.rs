@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct AssignQualityPolicy<'info> {
+    #[account(
+        mut,
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    /// Policy owned by the signing regulator
+    #[account(has_one = regulator @ QuotaError::UnauthorizedRegulator)]
+    pub quality_policy: Account<'info, QualityPolicy>,
+
+    pub regulator: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AssignQualityPolicy>) -> Result<()> {
+    authorize(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.regulator.key(),
+        None,
+        Action::AssignQualityPolicy,
+    )?;
+
+    let quota_account = &mut ctx.accounts.quota_account;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    quota_account.quality_policy = Some(ctx.accounts.quality_policy.key());
+    quota_account.updated_at = current_time;
+
+    // Emit event
+    emit!(QualityPolicyAssigned {
+        concession_id: quota_account.concession_id.clone(),
+        policy: ctx.accounts.quality_policy.key(),
+        policy_id: ctx.accounts.quality_policy.policy_id.clone(),
+        assigned_by: ctx.accounts.regulator.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
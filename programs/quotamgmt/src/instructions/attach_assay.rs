@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::get_instruction_relative;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct AttachAssay<'info> {
+    #[account(
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    #[account(
+        mut,
+        has_one = quota_account @ QuotaError::ShipmentQuotaMismatch,
+        constraint = UsageRecord::is_current_layout(usage_record.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub usage_record: Account<'info, UsageRecord>,
+
+    #[account(
+        seeds = [b"lab", lab_record.lab.as_ref()],
+        bump = lab_record.bump,
+        constraint = lab_record.is_active() @ QuotaError::LabNotActive
+    )]
+    pub lab_record: Account<'info, LabRecord>,
+
+    #[account(
+        seeds = [b"role", quota_account.key().as_ref(), authority.key().as_ref()],
+        bump = role_assignment.bump
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// Holder, dispatch clerk, inspector or regulator submitting the assay
+    pub authority: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, used to read the lab's Ed25519 signature
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+}
+
+pub fn handler(ctx: Context<AttachAssay>, sample_id: String, quality_params: QualityParameters) -> Result<()> {
+    authorize(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.authority.key(),
+        ctx.accounts.role_assignment.as_deref(),
+        Action::AttachAssay,
+    )?;
+
+    // Validation
+    require!(
+        sample_id.len() <= UsageRecord::MAX_SAMPLE_ID_LEN,
+        QuotaError::SampleIdTooLong
+    );
+    require!(
+        ctx.accounts.usage_record.assay_lab.is_none(),
+        QuotaError::AssayAlreadyAttached
+    );
+
+    // The lab must have signed exactly this payload
+    let payload = AssayPayload {
+        usage_record: ctx.accounts.usage_record.key(),
+        sample_id: sample_id.clone(),
+        quality_params: quality_params.clone(),
+    };
+    verify_lab_signature(
+        &ctx.accounts.instructions,
+        &ctx.accounts.lab_record.lab,
+        &payload.try_to_vec()?,
+    )?;
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let lab = ctx.accounts.lab_record.lab;
+    let usage_record = &mut ctx.accounts.usage_record;
+    let matches_declared = usage_record.quality_params.agrees_with(
+        &quality_params,
+        UsageRecord::ASSAY_GCV_TOLERANCE,
+        UsageRecord::ASSAY_PERCENTAGE_TOLERANCE,
+    );
+
+    usage_record.assay_lab = Some(lab);
+    usage_record.assay_sample_id = sample_id.clone();
+    usage_record.assayed_params = Some(quality_params.clone());
+    usage_record.assayed_at = current_time;
+
+    // A declaration the lab contradicts is flagged for the regulator, unless
+    // an inspector's earlier flag already stands
+    if !matches_declared && usage_record.flagged_by.is_none() {
+        let reason = format!("Lab assay {} does not match declared quality", sample_id);
+        usage_record.flagged_by = Some(lab);
+        usage_record.flag_reason = reason.clone();
+        usage_record.flagged_at = current_time;
+
+        emit!(ShipmentFlagged {
+            concession_id: usage_record.concession_id.clone(),
+            shipment_id: usage_record.shipment_id.clone(),
+            flagged_by: lab,
+            role: None,
+            reason,
+            timestamp: current_time,
+        });
+    }
+
+    // Emit event
+    emit!(AssayAttached {
+        concession_id: usage_record.concession_id.clone(),
+        shipment_id: usage_record.shipment_id.clone(),
+        sequence: usage_record.sequence,
+        lab,
+        sample_id,
+        assayed_params: quality_params,
+        matches_declared,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+/// Size of one signature's offsets entry in Ed25519 program instruction data
+const ED25519_OFFSETS_LEN: usize = 14;
+/// Ed25519 offsets pointing into the Ed25519 instruction's own data
+const ED25519_SAME_INSTRUCTION: u16 = u16::MAX;
+
+/// Ensure the instruction preceding this one is an Ed25519 program
+/// instruction carrying a single signature by `signer` over `message`. The
+/// runtime has already checked the signature itself when it ran.
+fn verify_lab_signature(instructions: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    let ed25519_ix = get_instruction_relative(-1, instructions)
+        .map_err(|_| error!(QuotaError::InvalidAssaySignature))?;
+    require_keys_eq!(
+        ed25519_ix.program_id,
+        ed25519_program::ID,
+        QuotaError::InvalidAssaySignature
+    );
+
+    let data = &ed25519_ix.data;
+    require!(
+        data.len() >= 2 + ED25519_OFFSETS_LEN && data[0] == 1,
+        QuotaError::InvalidAssaySignature
+    );
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[2 + at], data[3 + at]]);
+    let signature_instruction = read_u16(2);
+    let public_key_offset = read_u16(4) as usize;
+    let public_key_instruction = read_u16(6);
+    let message_offset = read_u16(8) as usize;
+    let message_len = read_u16(10) as usize;
+    let message_instruction = read_u16(12);
+
+    require!(
+        signature_instruction == ED25519_SAME_INSTRUCTION
+            && public_key_instruction == ED25519_SAME_INSTRUCTION
+            && message_instruction == ED25519_SAME_INSTRUCTION,
+        QuotaError::InvalidAssaySignature
+    );
+    require!(
+        data.get(public_key_offset..public_key_offset + 32) == Some(signer.as_ref())
+            && data.get(message_offset..message_offset + message_len) == Some(message),
+        QuotaError::InvalidAssaySignature
+    );
+
+    Ok(())
+}
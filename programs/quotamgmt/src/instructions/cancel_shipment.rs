@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+use super::close_quota_account::is_closed_account;
+
+#[derive(Accounts)]
+pub struct CancelShipment<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    #[account(
+        mut,
+        has_one = quota_account @ QuotaError::ShipmentQuotaMismatch,
+        constraint = UsageRecord::is_current_layout(usage_record.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub usage_record: Account<'info, UsageRecord>,
+
+    /// CHECK: Environmental clearance of the quota, refunded in the handler
+    /// if one has been registered at this address
+    #[account(
+        mut,
+        seeds = [b"clearance", quota_account.key().as_ref()],
+        bump
+    )]
+    pub clearance: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"role", quota_account.key().as_ref(), authority.key().as_ref()],
+        bump = role_assignment.bump
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        seeds = [b"delegate", quota_account.key().as_ref(), authority.key().as_ref()],
+        bump = delegate.bump
+    )]
+    pub delegate: Option<Account<'info, Delegate>>,
+
+    /// CHECK: Delegate the shipment was charged to, required when the usage
+    /// record names one; bound by address and deserialized in the handler
+    #[account(
+        mut,
+        constraint = usage_record.delegate == Some(dispatch_delegate.key()) @ QuotaError::DelegateMismatch
+    )]
+    pub dispatch_delegate: Option<UncheckedAccount<'info>>,
+
+    /// Holder, dispatch clerk or delegate of the quota
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelShipment>) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+
+    authorize_with_delegate(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.authority.key(),
+        ctx.accounts.role_assignment.as_deref(),
+        ctx.accounts.delegate.as_deref(),
+        current_time,
+        Action::CancelShipment,
+    )?;
+
+    let usage_record = &mut ctx.accounts.usage_record;
+
+    // Validation
+    require!(
+        current_time <= usage_record.timestamp + ctx.accounts.program_config.cancellation_window,
+        QuotaError::CancellationWindowClosed
+    );
+    usage_record.transition(ShipmentStatus::Cancelled, current_time)?;
+
+    // Refund the tonnage to the quota
+    let quota_account = &mut ctx.accounts.quota_account;
+    let amount = usage_record.amount;
+    quota_account.refund_usage(amount);
+    if usage_record.grade_debited {
+        quota_account.credit_grade(&usage_record.quality_params.coal_grade, amount);
+    }
+    quota_account.settle_shipment();
+    quota_account.updated_at = current_time;
+
+    // Give the clearance back its capacity, if the quota has one
+    let clearance = &ctx.accounts.clearance;
+    if !is_closed_account(clearance) {
+        let mut data = clearance.try_borrow_mut_data()?;
+        let mut environmental_clearance = EnvironmentalClearance::try_deserialize(&mut &data[..])?;
+        environmental_clearance.refund(amount, usage_record.timestamp);
+        environmental_clearance.try_serialize(&mut &mut data[..])?;
+    }
+
+    // Give the delegate back the allowance; a revoked delegate has nothing to refund
+    if usage_record.delegate.is_some() {
+        let dispatch_delegate = ctx
+            .accounts
+            .dispatch_delegate
+            .as_ref()
+            .ok_or(QuotaError::DelegateMismatch)?;
+        if !is_closed_account(dispatch_delegate) {
+            let mut data = dispatch_delegate.try_borrow_mut_data()?;
+            let mut delegate = Delegate::try_deserialize(&mut &data[..])?;
+            delegate.refund(amount, usage_record.timestamp);
+            delegate.try_serialize(&mut &mut data[..])?;
+        }
+    }
+
+    // A refund can bring an exhausted quota back into use while it is still valid
+    if quota_account.status == QuotaStatus::Exhausted
+        && quota_account.available_quota > 0
+        && current_time <= quota_account.validity_period
+    {
+        quota_account.status = QuotaStatus::Active;
+    }
+
+    // Emit event
+    emit!(ShipmentCancelled {
+        concession_id: quota_account.concession_id.clone(),
+        shipment_id: usage_record.shipment_id.clone(),
+        sequence: usage_record.sequence,
+        refunded_amount: amount,
+        available_quota: quota_account.available_quota,
+        coal_grade: usage_record.quality_params.coal_grade.clone(),
+        cancelled_by: ctx.accounts.authority.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct CancelTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [b"quota", from_quota.concession_id.as_bytes(), from_quota.holder.as_ref()],
+        bump = from_quota.bump,
+        constraint = QuotaAccount::is_current_layout(from_quota.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub from_quota: Account<'info, QuotaAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"transfer",
+            transfer_record.from_quota.as_ref(),
+            &transfer_record.sequence.to_le_bytes()
+        ],
+        bump = transfer_record.bump,
+        has_one = from_quota @ QuotaError::TransferQuotaMismatch
+    )]
+    pub transfer_record: Account<'info, TransferRecord>,
+
+    /// Holder of the source quota, or the regulator who issued it
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelTransfer>) -> Result<()> {
+    authorize(
+        &ctx.accounts.from_quota,
+        ctx.accounts.from_quota.key(),
+        ctx.accounts.authority.key(),
+        None,
+        Action::CancelTransfer,
+    )?;
+
+    let transfer_record = &mut ctx.accounts.transfer_record;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Can only withdraw transfers that are still in flight
+    require!(
+        matches!(
+            transfer_record.status,
+            TransferStatus::Requested | TransferStatus::Approved | TransferStatus::Accepted
+        ),
+        QuotaError::InvalidTransferStatus
+    );
+
+    let old_status = transfer_record.status.clone();
+    transfer_record.status = TransferStatus::Cancelled;
+    ctx.accounts.from_quota.settle_transfer();
+
+    // Emit event
+    emit!(TransferStatusUpdated {
+        from_concession: transfer_record.from_concession.clone(),
+        to_concession: transfer_record.to_concession.clone(),
+        old_status,
+        new_status: TransferStatus::Cancelled,
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use super::close_quota_account::is_retired_quota;
+
+#[derive(Accounts)]
+pub struct CloseClearance<'info> {
+    /// CHECK: Only inspected to see whether it has been revoked or closed;
+    /// bound to the clearance by address
+    #[account(address = clearance.quota_account @ QuotaError::ClearanceQuotaMismatch)]
+    pub quota_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"clearance", clearance.quota_account.as_ref()],
+        bump = clearance.bump,
+        constraint = clearance.registered_by == payer.key() @ QuotaError::PayerMismatch,
+        close = payer
+    )]
+    pub clearance: Account<'info, EnvironmentalClearance>,
+
+    /// CHECK: Regulator who registered, and paid for, the clearance; bound
+    /// by constraint
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// Anyone may close a retired quota's clearance, as the rent can only go
+    /// back to whoever paid it
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseClearance>) -> Result<()> {
+    let clearance = &ctx.accounts.clearance;
+
+    // Validation
+    require!(
+        is_retired_quota(&ctx.accounts.quota_account)?,
+        QuotaError::QuotaNotRetired
+    );
+
+    // Emit event
+    emit!(ClearanceArchived {
+        quota_account: clearance.quota_account,
+        reference_number: clearance.reference_number.clone(),
+        annual_capacity: clearance.annual_capacity,
+        used_this_year: clearance.used_this_year,
+        closed_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
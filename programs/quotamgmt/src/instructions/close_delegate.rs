@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use super::close_quota_account::is_retired_quota;
+
+#[derive(Accounts)]
+pub struct CloseDelegate<'info> {
+    /// CHECK: Only inspected to see whether it has been revoked or closed;
+    /// bound to the delegate by address
+    #[account(address = delegate.quota_account @ QuotaError::DelegateQuotaMismatch)]
+    pub quota_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = payer @ QuotaError::PayerMismatch,
+        close = payer,
+        seeds = [b"delegate", delegate.quota_account.as_ref(), delegate.delegate.as_ref()],
+        bump = delegate.bump
+    )]
+    pub delegate: Account<'info, Delegate>,
+
+    /// CHECK: Original payer of the delegate's rent, bound by `has_one`
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// Anyone may close a retired quota's delegates, as the rent can only go
+    /// back to whoever paid it
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseDelegate>) -> Result<()> {
+    let delegate = &ctx.accounts.delegate;
+
+    // Validation
+    require!(
+        is_retired_quota(&ctx.accounts.quota_account)?,
+        QuotaError::QuotaNotRetired
+    );
+
+    // Emit event
+    emit!(DelegateArchived {
+        quota_account: delegate.quota_account,
+        delegate: delegate.delegate,
+        used_tonnage: delegate.used_tonnage,
+        closed_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+use super::use_quota_batch::create_program_account;
+
+#[derive(Accounts)]
+pub struct CloseQuotaAccount<'info> {
+    #[account(
+        mut,
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired,
+        close = regulator
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    /// CHECK: Tombstone of the quota, created on its first close and
+    /// rewritten on later ones in the handler
+    #[account(
+        mut,
+        seeds = [b"tombstone", quota_account.key().as_ref()],
+        bump
+    )]
+    pub tombstone: UncheckedAccount<'info>,
+
+    /// Regulator who issued, and paid for, the quota; pays for the tombstone
+    #[account(mut)]
+    pub regulator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CloseQuotaAccount>) -> Result<()> {
+    authorize(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.regulator.key(),
+        None,
+        Action::CloseQuota,
+    )?;
+
+    let quota_account = &ctx.accounts.quota_account;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Validation
+    require!(quota_account.is_closable(), QuotaError::QuotaNotClosable);
+
+    // Keep the record counters, as a quota re-issued at this address must
+    // not reuse the addresses of records that are still retained
+    let quota_key = quota_account.key();
+    let tombstone_info = ctx.accounts.tombstone.to_account_info();
+    if is_closed_account(&tombstone_info) {
+        create_program_account(
+            &tombstone_info,
+            &ctx.accounts.regulator,
+            &ctx.accounts.system_program,
+            &[b"tombstone", quota_key.as_ref(), &[ctx.bumps.tombstone]],
+            QuotaTombstone::LEN,
+        )?;
+    }
+    let tombstone = QuotaTombstone {
+        quota_account: quota_key,
+        transfer_count: quota_account.transfer_count,
+        usage_count: quota_account.usage_count,
+        reservation_count: quota_account.reservation_count,
+        closed_at: current_time,
+        bump: ctx.bumps.tombstone,
+    };
+    tombstone.try_serialize(&mut &mut tombstone_info.try_borrow_mut_data()?[..])?;
+
+    // Emit event
+    emit!(QuotaArchived {
+        concession_id: quota_account.concession_id.clone(),
+        holder: quota_account.holder,
+        allocated_quota: quota_account.allocated_quota,
+        used_quota: quota_account.used_quota,
+        available_quota: quota_account.available_quota,
+        status: quota_account.status.clone(),
+        closed_by: ctx.accounts.regulator.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+/// Whether a quota account has been closed, leaving its usage and transfer
+/// records without a parent that could settle them
+pub(crate) fn is_closed_account(account: &AccountInfo) -> bool {
+    account.owner != &crate::ID || account.data_is_empty()
+}
+
+/// Whether a quota account has been revoked or closed, leaving the
+/// clearance, roles and delegates hanging off it with no further use
+pub(crate) fn is_retired_quota(account: &AccountInfo) -> Result<bool> {
+    if is_closed_account(account) {
+        return Ok(true);
+    }
+    let quota_account = QuotaAccount::decode_stored(&account.try_borrow_data()?)?;
+    Ok(quota_account.status == QuotaStatus::Revoked)
+}
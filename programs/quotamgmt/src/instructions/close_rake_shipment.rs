@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use super::close_quota_account::is_closed_account;
+
+#[derive(Accounts)]
+pub struct CloseRakeShipment<'info> {
+    /// CHECK: Only inspected to see whether it has been closed; bound to the
+    /// rake by address
+    #[account(address = rake_shipment.usage_record @ QuotaError::ShipmentQuotaMismatch)]
+    pub usage_record: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"rake", rake_shipment.usage_record.as_ref()],
+        bump = rake_shipment.bump,
+        constraint = rake_shipment.dispatched_by == payer.key() @ QuotaError::PayerMismatch,
+        close = payer
+    )]
+    pub rake_shipment: Account<'info, RakeShipment>,
+
+    /// CHECK: Holder or dispatch clerk who dispatched, and paid for, the
+    /// rake; bound by constraint
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// Anyone may close a rake once its usage record is gone, as the rent
+    /// can only go back to whoever paid it
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseRakeShipment>) -> Result<()> {
+    let rake_shipment = &ctx.accounts.rake_shipment;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Validation: the usage record is only closed once settled and past
+    // retention, or orphaned by a closed quota
+    require!(
+        is_closed_account(&ctx.accounts.usage_record),
+        QuotaError::RecordNotClosable
+    );
+
+    // Emit event
+    emit!(RakeShipmentArchived {
+        quota_account: rake_shipment.quota_account,
+        usage_record: rake_shipment.usage_record,
+        rake_number: rake_shipment.rake_number.clone(),
+        siding_code: rake_shipment.siding_code.clone(),
+        wagon_count: rake_shipment.wagon_count,
+        net_weight_kg: rake_shipment.net_weight_kg,
+        verified_count: rake_shipment.verified_count,
+        verified_net_weight_kg: rake_shipment.verified_net_weight_kg,
+        measured_net_weight_kg: rake_shipment.measured_net_weight_kg,
+        discrepancies: rake_shipment.discrepancies,
+        closed_by: ctx.accounts.authority.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use super::close_quota_account::is_retired_quota;
+
+#[derive(Accounts)]
+pub struct CloseRoleAssignment<'info> {
+    /// CHECK: Only inspected to see whether it has been revoked or closed;
+    /// bound to the assignment by address
+    #[account(address = role_assignment.quota_account @ QuotaError::RoleQuotaMismatch)]
+    pub quota_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = payer @ QuotaError::PayerMismatch,
+        close = payer,
+        seeds = [b"role", role_assignment.quota_account.as_ref(), role_assignment.member.as_ref()],
+        bump = role_assignment.bump
+    )]
+    pub role_assignment: Account<'info, RoleAssignment>,
+
+    /// CHECK: Original payer of the assignment's rent, bound by `has_one`
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// Anyone may close a retired quota's role assignments, as the rent can
+    /// only go back to whoever paid it
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseRoleAssignment>) -> Result<()> {
+    let role_assignment = &ctx.accounts.role_assignment;
+
+    // Validation
+    require!(
+        is_retired_quota(&ctx.accounts.quota_account)?,
+        QuotaError::QuotaNotRetired
+    );
+
+    // Emit event
+    emit!(RoleAssignmentArchived {
+        quota_account: role_assignment.quota_account,
+        member: role_assignment.member,
+        role: role_assignment.role,
+        closed_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use super::close_quota_account::is_closed_account;
+
+#[derive(Accounts)]
+pub struct CloseTransferRecord<'info> {
+    /// CHECK: Only inspected to see whether it has been closed; bound to the
+    /// record by address
+    #[account(address = transfer_record.from_quota @ QuotaError::TransferQuotaMismatch)]
+    pub from_quota: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"transfer",
+            transfer_record.from_quota.as_ref(),
+            &transfer_record.sequence.to_le_bytes()
+        ],
+        bump = transfer_record.bump,
+        constraint = transfer_record.requested_by == holder.key() @ QuotaError::UnauthorizedHolder,
+        close = holder
+    )]
+    pub transfer_record: Account<'info, TransferRecord>,
+
+    /// Source holder who requested, and paid for, the transfer record
+    #[account(mut)]
+    pub holder: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseTransferRecord>) -> Result<()> {
+    let transfer_record = &ctx.accounts.transfer_record;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Validation: settled and past retention, or orphaned by a closed quota
+    require!(
+        (transfer_record.is_settled()
+            && current_time >= transfer_record.last_activity_at() + TransferRecord::RETENTION_PERIOD)
+            || is_closed_account(&ctx.accounts.from_quota),
+        QuotaError::RecordNotClosable
+    );
+
+    // Emit event
+    emit!(TransferRecordArchived {
+        from_quota: transfer_record.from_quota,
+        to_quota: transfer_record.to_quota,
+        sequence: transfer_record.sequence,
+        amount: transfer_record.amount,
+        status: transfer_record.status.clone(),
+        authorized_by: transfer_record.authorized_by,
+        requested_at: transfer_record.timestamp,
+        executed_at: transfer_record.executed_at,
+        closed_by: ctx.accounts.holder.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
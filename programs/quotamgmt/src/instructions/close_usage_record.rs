@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use super::close_quota_account::is_closed_account;
+
+#[derive(Accounts)]
+pub struct CloseUsageRecord<'info> {
+    /// CHECK: Only inspected to see whether it has been closed; bound to the
+    /// record by address
+    #[account(address = usage_record.quota_account @ QuotaError::ShipmentQuotaMismatch)]
+    pub quota_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = holder @ QuotaError::UnauthorizedHolder,
+        has_one = payer @ QuotaError::PayerMismatch,
+        constraint = UsageRecord::is_current_layout(usage_record.as_ref()) @ QuotaError::AccountMigrationRequired,
+        close = payer
+    )]
+    pub usage_record: Account<'info, UsageRecord>,
+
+    /// CHECK: Original payer of the record's rent, bound by `has_one`
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    pub holder: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseUsageRecord>) -> Result<()> {
+    let usage_record = &ctx.accounts.usage_record;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Validation: settled and past retention, or orphaned by a closed quota
+    let retained_until = usage_record
+        .finalised_at()
+        .map(|finalised_at| finalised_at + UsageRecord::RETENTION_PERIOD);
+    require!(
+        retained_until.is_some_and(|until| current_time >= until)
+            || is_closed_account(&ctx.accounts.quota_account),
+        QuotaError::RecordNotClosable
+    );
+
+    // Emit event
+    emit!(UsageRecordArchived {
+        quota_account: usage_record.quota_account,
+        concession_id: usage_record.concession_id.clone(),
+        shipment_id: usage_record.shipment_id.clone(),
+        sequence: usage_record.sequence,
+        amount: usage_record.amount,
+        quality_params: usage_record.quality_params.clone(),
+        source_location: usage_record.source_location.clone(),
+        destination_location: usage_record.destination_location.clone(),
+        transport_details: usage_record.transport_details.clone(),
+        status: usage_record.status.clone(),
+        dispatched_at: usage_record.timestamp,
+        finalised_at: usage_record.finalised_at().unwrap_or(0),
+        flagged_by: usage_record.flagged_by,
+        closed_by: ctx.accounts.holder.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ConfirmDelivery<'info> {
+    #[account(
+        mut,
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    #[account(
+        mut,
+        has_one = quota_account @ QuotaError::ShipmentQuotaMismatch,
+        constraint = UsageRecord::is_current_layout(usage_record.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub usage_record: Account<'info, UsageRecord>,
+
+    #[account(
+        seeds = [b"role", quota_account.key().as_ref(), authority.key().as_ref()],
+        bump = role_assignment.bump
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// Holder, dispatch clerk, inspector or regulator of the quota
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ConfirmDelivery>) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+
+    authorize(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.authority.key(),
+        ctx.accounts.role_assignment.as_deref(),
+        Action::ConfirmDelivery,
+    )?;
+
+    let usage_record = &mut ctx.accounts.usage_record;
+    let old_status = usage_record.status.clone();
+    usage_record.transition(ShipmentStatus::Delivered, current_time)?;
+    ctx.accounts.quota_account.settle_shipment();
+
+    // Emit event
+    emit!(ShipmentStatusUpdated {
+        concession_id: usage_record.concession_id.clone(),
+        shipment_id: usage_record.shipment_id.clone(),
+        sequence: usage_record.sequence,
+        old_status,
+        new_status: ShipmentStatus::Delivered,
+        updated_by: ctx.accounts.authority.key(),
+        reason: String::new(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+use super::use_quota::{record_usage, Shipment};
+
+#[derive(Accounts)]
+pub struct ConsumeReservation<'info> {
+    #[account(
+        mut,
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    #[account(
+        mut,
+        has_one = quota_account @ QuotaError::ReservationQuotaMismatch,
+        has_one = payer @ QuotaError::PayerMismatch,
+        close = payer
+    )]
+    pub reservation: Account<'info, Reservation>,
+
+    /// CHECK: Original payer of the reservation's rent, bound by `has_one`
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = UsageRecord::LEN,
+        seeds = [
+            b"usage",
+            quota_account.key().as_ref(),
+            &quota_account.usage_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub usage_record: Account<'info, UsageRecord>,
+
+    #[account(
+        seeds = [b"role", quota_account.key().as_ref(), authority.key().as_ref()],
+        bump = role_assignment.bump
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// CHECK: Environmental clearance of the quota, charged in the handler
+    /// once one has been registered at this address
+    #[account(
+        mut,
+        seeds = [b"clearance", quota_account.key().as_ref()],
+        bump
+    )]
+    pub clearance: UncheckedAccount<'info>,
+
+    /// Quality policy assigned to the quota, if any
+    pub quality_policy: Option<Account<'info, QualityPolicy>>,
+
+    /// Holder or dispatch clerk of the quota, pays for the usage record
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<ConsumeReservation>,
+    shipment_id: String,
+    quality_params: QualityParameters,
+) -> Result<()> {
+    authorize(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.authority.key(),
+        ctx.accounts.role_assignment.as_deref(),
+        Action::UseQuota,
+    )?;
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let reservation = &ctx.accounts.reservation;
+    let amount = reservation.amount;
+
+    // Validation
+    require!(!reservation.is_expired(current_time), QuotaError::ReservationExpired);
+
+    // Lift the hold and dispatch the held tonnage like any other shipment
+    ctx.accounts.quota_account.release_hold(amount);
+    let quota_key = ctx.accounts.quota_account.key();
+    let usage_sequence = ctx.accounts.quota_account.usage_count;
+    record_usage(
+        quota_key,
+        &mut ctx.accounts.quota_account,
+        &mut ctx.accounts.usage_record,
+        ctx.bumps.usage_record,
+        &ctx.accounts.clearance,
+        ctx.accounts.quality_policy.as_ref(),
+        Shipment {
+            amount,
+            shipment_id: shipment_id.clone(),
+            quality_params,
+            payer: ctx.accounts.authority.key(),
+            delegate: None,
+        },
+    )?;
+
+    // Emit event
+    emit!(ReservationConsumed {
+        concession_id: ctx.accounts.quota_account.concession_id.clone(),
+        sequence: ctx.accounts.reservation.sequence,
+        shipment_id,
+        usage_sequence,
+        amount,
+        consumed_by: ctx.accounts.authority.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct CreateDelegate<'info> {
+    #[account(
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    #[account(
+        init,
+        payer = holder,
+        space = Delegate::LEN,
+        seeds = [b"delegate", quota_account.key().as_ref(), delegate_authority.key().as_ref()],
+        bump
+    )]
+    pub delegate: Account<'info, Delegate>,
+
+    /// CHECK: Dispatch key being authorised
+    pub delegate_authority: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateDelegate>,
+    max_tonnage: u64,
+    daily_cap: u64,
+    expires_at: i64,
+) -> Result<()> {
+    authorize(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.holder.key(),
+        None,
+        Action::ManageDelegates,
+    )?;
+
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Validation
+    require!(
+        max_tonnage > 0 && daily_cap > 0 && daily_cap <= max_tonnage,
+        QuotaError::InvalidDelegateLimits
+    );
+    require!(expires_at > current_time, QuotaError::InvalidValidityPeriod);
+
+    let quota_account = &ctx.accounts.quota_account;
+    let delegate = &mut ctx.accounts.delegate;
+    delegate.quota_account = quota_account.key();
+    delegate.delegate = ctx.accounts.delegate_authority.key();
+    delegate.max_tonnage = max_tonnage;
+    delegate.used_tonnage = 0;
+    delegate.daily_cap = daily_cap;
+    delegate.current_day = current_time.div_euclid(Delegate::SECONDS_PER_DAY);
+    delegate.used_today = 0;
+    delegate.expires_at = expires_at;
+    delegate.created_at = current_time;
+    delegate.bump = ctx.bumps.delegate;
+    delegate.payer = ctx.accounts.holder.key();
+
+    // Emit event
+    emit!(DelegateCreated {
+        concession_id: quota_account.concession_id.clone(),
+        delegate: delegate.delegate,
+        max_tonnage,
+        daily_cap,
+        expires_at,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
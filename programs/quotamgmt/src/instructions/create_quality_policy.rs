@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(policy_id: String)]
+pub struct CreateQualityPolicy<'info> {
+    #[account(
+        init,
+        payer = regulator,
+        space = QualityPolicy::LEN,
+        seeds = [b"quality_policy", regulator.key().as_ref(), policy_id.as_bytes()],
+        bump
+    )]
+    pub quality_policy: Account<'info, QualityPolicy>,
+
+    #[account(
+        seeds = [b"regulator", regulator.key().as_ref()],
+        bump = regulator_record.bump,
+        constraint = regulator_record.is_active() @ QuotaError::RegulatorNotActive
+    )]
+    pub regulator_record: Account<'info, RegulatorRecord>,
+
+    #[account(mut)]
+    pub regulator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateQualityPolicy>,
+    policy_id: String,
+    region: String,
+    bounds: QualityBounds,
+) -> Result<()> {
+    // Validation
+    require!(
+        policy_id.len() <= QualityPolicy::MAX_POLICY_ID_LEN,
+        QuotaError::PolicyIdTooLong
+    );
+    require!(
+        region.len() <= QualityPolicy::MAX_REGION_LEN,
+        QuotaError::MiningRegionTooLong
+    );
+    require!(bounds.is_consistent(), QuotaError::InvalidQualityParameters);
+
+    let quality_policy = &mut ctx.accounts.quality_policy;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    quality_policy.regulator = ctx.accounts.regulator.key();
+    quality_policy.policy_id = policy_id.clone();
+    quality_policy.region = region.clone();
+    quality_policy.bounds = bounds.clone();
+    quality_policy.created_at = current_time;
+    quality_policy.updated_at = current_time;
+    quality_policy.bump = ctx.bumps.quality_policy;
+
+    // Emit event
+    emit!(QualityPolicyCreated {
+        regulator: quality_policy.regulator,
+        policy_id,
+        region,
+        bounds,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct DisableRegulator<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+        has_one = admin @ QuotaError::UnauthorizedAdmin
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"regulator", regulator_record.regulator.as_ref()],
+        bump = regulator_record.bump
+    )]
+    pub regulator_record: Account<'info, RegulatorRecord>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<DisableRegulator>) -> Result<()> {
+    let regulator_record = &mut ctx.accounts.regulator_record;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Can only disable active regulators
+    require!(
+        regulator_record.status == RegulatorStatus::Active,
+        QuotaError::RegulatorNotActive
+    );
+
+    let old_status = regulator_record.status.clone();
+    regulator_record.status = RegulatorStatus::Disabled;
+    regulator_record.updated_at = current_time;
+
+    // Emit event
+    emit!(RegulatorStatusUpdated {
+        regulator: regulator_record.regulator,
+        old_status,
+        new_status: RegulatorStatus::Disabled,
+        updated_by: ctx.accounts.admin.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
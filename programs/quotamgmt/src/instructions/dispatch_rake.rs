@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+use super::use_quota::{record_usage, Shipment};
+
+#[derive(Accounts)]
+pub struct DispatchRake<'info> {
+    #[account(
+        mut,
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = UsageRecord::LEN,
+        seeds = [
+            b"usage",
+            quota_account.key().as_ref(),
+            &quota_account.usage_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub usage_record: Account<'info, UsageRecord>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = RakeShipment::LEN,
+        seeds = [b"rake", usage_record.key().as_ref()],
+        bump
+    )]
+    pub rake_shipment: Account<'info, RakeShipment>,
+
+    #[account(
+        seeds = [b"role", quota_account.key().as_ref(), authority.key().as_ref()],
+        bump = role_assignment.bump
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// CHECK: Environmental clearance of the quota, charged in the handler
+    /// once one has been registered at this address
+    #[account(
+        mut,
+        seeds = [b"clearance", quota_account.key().as_ref()],
+        bump
+    )]
+    pub clearance: UncheckedAccount<'info>,
+
+    /// Quality policy assigned to the quota, if any
+    pub quality_policy: Option<Account<'info, QualityPolicy>>,
+
+    /// Holder or dispatch clerk of the quota, pays for the usage record and rake
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<DispatchRake>,
+    rake_number: String,
+    siding_code: String,
+    wagon_count: u16,
+    net_weight_kg: u64,
+    wagon_root: [u8; 32],
+    quality_params: QualityParameters,
+) -> Result<()> {
+    authorize(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.authority.key(),
+        ctx.accounts.role_assignment.as_deref(),
+        Action::UseQuota,
+    )?;
+
+    // Validation
+    require!(
+        siding_code.len() <= RakeShipment::MAX_SIDING_CODE_LEN,
+        QuotaError::SidingCodeTooLong
+    );
+    require!(
+        wagon_count > 0 && wagon_count <= RakeShipment::MAX_WAGONS && net_weight_kg > 0,
+        QuotaError::InvalidRakeParameters
+    );
+
+    // The whole rake is charged to the quota once, as a single usage record
+    let current_time = Clock::get()?.unix_timestamp;
+    let amount = RakeShipment::tonnage(net_weight_kg);
+    let quota_key = ctx.accounts.quota_account.key();
+    let sequence = ctx.accounts.quota_account.usage_count;
+    record_usage(
+        quota_key,
+        &mut ctx.accounts.quota_account,
+        &mut ctx.accounts.usage_record,
+        ctx.bumps.usage_record,
+        &ctx.accounts.clearance,
+        ctx.accounts.quality_policy.as_ref(),
+        Shipment {
+            amount,
+            shipment_id: rake_number.clone(),
+            quality_params,
+            payer: ctx.accounts.authority.key(),
+            delegate: None,
+        },
+    )?;
+
+    let rake_shipment = &mut ctx.accounts.rake_shipment;
+    rake_shipment.quota_account = quota_key;
+    rake_shipment.usage_record = ctx.accounts.usage_record.key();
+    rake_shipment.rake_number = rake_number.clone();
+    rake_shipment.siding_code = siding_code.clone();
+    rake_shipment.wagon_count = wagon_count;
+    rake_shipment.net_weight_kg = net_weight_kg;
+    rake_shipment.wagon_root = wagon_root;
+    rake_shipment.verified_wagons = [0; 16];
+    rake_shipment.verified_count = 0;
+    rake_shipment.verified_net_weight_kg = 0;
+    rake_shipment.measured_net_weight_kg = 0;
+    rake_shipment.discrepancies = 0;
+    rake_shipment.dispatched_by = ctx.accounts.authority.key();
+    rake_shipment.created_at = current_time;
+    rake_shipment.bump = ctx.bumps.rake_shipment;
+
+    // Emit event
+    emit!(RakeDispatched {
+        concession_id: ctx.accounts.quota_account.concession_id.clone(),
+        rake_number,
+        siding_code,
+        sequence,
+        wagon_count,
+        net_weight_kg,
+        amount,
+        wagon_root,
+        dispatched_by: ctx.accounts.authority.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
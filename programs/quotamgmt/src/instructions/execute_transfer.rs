@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ExecuteTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [b"quota", from_quota.concession_id.as_bytes(), from_quota.holder.as_ref()],
+        bump = from_quota.bump,
+        constraint = QuotaAccount::is_current_layout(from_quota.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub from_quota: Account<'info, QuotaAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"quota", to_quota.concession_id.as_bytes(), to_quota.holder.as_ref()],
+        bump = to_quota.bump,
+        constraint = QuotaAccount::is_current_layout(to_quota.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub to_quota: Account<'info, QuotaAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"transfer",
+            transfer_record.from_quota.as_ref(),
+            &transfer_record.sequence.to_le_bytes()
+        ],
+        bump = transfer_record.bump,
+        has_one = from_quota @ QuotaError::TransferQuotaMismatch,
+        has_one = to_quota @ QuotaError::TransferQuotaMismatch
+    )]
+    pub transfer_record: Account<'info, TransferRecord>,
+
+    /// Anyone may execute once the regulator and the recipient have agreed
+    pub executor: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ExecuteTransfer>) -> Result<()> {
+    let from_quota = &mut ctx.accounts.from_quota;
+    let to_quota = &mut ctx.accounts.to_quota;
+    let transfer_record = &mut ctx.accounts.transfer_record;
+    let current_time = Clock::get()?.unix_timestamp;
+    let amount = transfer_record.amount;
+
+    // Validation
+    require!(
+        transfer_record.status == TransferStatus::Accepted,
+        QuotaError::InvalidTransferStatus
+    );
+    require!(from_quota.status == QuotaStatus::Active, QuotaError::QuotaNotActive);
+    require!(to_quota.status == QuotaStatus::Active, QuotaError::QuotaNotActive);
+    require!(
+        from_quota.available_quota >= amount,
+        QuotaError::TransferAmountExceedsAvailable
+    );
+    require!(
+        from_quota.allocated_quota - amount >= from_quota.grade_allocated_total(),
+        QuotaError::GradeAllocationExceedsQuota
+    );
+
+    // Check validity periods
+    require!(current_time <= from_quota.validity_period, QuotaError::QuotaExpired);
+    require!(current_time <= to_quota.validity_period, QuotaError::QuotaExpired);
+
+    // Update quotas
+    from_quota.available_quota -= amount;
+    from_quota.allocated_quota -= amount;
+    from_quota.settle_transfer();
+    from_quota.updated_at = current_time;
+
+    to_quota.available_quota += amount;
+    to_quota.allocated_quota += amount;
+    to_quota.updated_at = current_time;
+
+    // Update status if quota is exhausted; tonnage still on hold may yet be dispatched
+    if from_quota.available_quota == 0 && from_quota.reserved_quota == 0 {
+        from_quota.status = QuotaStatus::Exhausted;
+    }
+
+    transfer_record.status = TransferStatus::Executed;
+    transfer_record.executed_at = current_time;
+
+    // Emit event
+    emit!(QuotaTransferred {
+        from_concession: from_quota.concession_id.clone(),
+        to_concession: to_quota.concession_id.clone(),
+        amount,
+        transfer_type: transfer_record.transfer_type.clone(),
+        authorized_by: transfer_record.authorized_by,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ExpireQuota<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    /// Anyone may crank an elapsed quota and collects the crank reward
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ExpireQuota>) -> Result<()> {
+    let quota_account = &mut ctx.accounts.quota_account;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Validation
+    require!(
+        current_time > quota_account.validity_period,
+        QuotaError::ValidityPeriodNotElapsed
+    );
+    require!(
+        quota_account.status != QuotaStatus::Expired
+            && quota_account.status != QuotaStatus::Revoked,
+        QuotaError::QuotaAlreadyClosed
+    );
+
+    let old_status = quota_account.status.clone();
+    quota_account.status = QuotaStatus::Expired;
+    quota_account.updated_at = current_time;
+
+    // Pay the crank reward only while the config holds more than its rent
+    // reserve, so an unfunded pool never blocks expiry
+    let reward = ctx.accounts.program_config.crank_reward;
+    if reward > 0 {
+        let config_info = ctx.accounts.program_config.to_account_info();
+        let rent_reserve = Rent::get()?.minimum_balance(config_info.data_len());
+        if config_info.lamports() >= rent_reserve + reward {
+            **config_info.try_borrow_mut_lamports()? -= reward;
+            **ctx.accounts.cranker.to_account_info().try_borrow_mut_lamports()? += reward;
+        }
+    }
+
+    // Emit event
+    emit!(QuotaStatusUpdated {
+        concession_id: quota_account.concession_id.clone(),
+        old_status,
+        new_status: QuotaStatus::Expired,
+        updated_by: ctx.accounts.cranker.key(),
+        reason: "Validity period elapsed".to_string(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct FlagShipment<'info> {
+    #[account(
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    #[account(
+        mut,
+        has_one = quota_account @ QuotaError::ShipmentQuotaMismatch,
+        constraint = UsageRecord::is_current_layout(usage_record.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub usage_record: Account<'info, UsageRecord>,
+
+    #[account(
+        seeds = [b"role", quota_account.key().as_ref(), authority.key().as_ref()],
+        bump = role_assignment.bump
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// Inspector, auditor or regulator of the quota
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<FlagShipment>, reason: String) -> Result<()> {
+    require!(
+        reason.len() <= UsageRecord::MAX_FLAG_REASON_LEN,
+        QuotaError::ReasonTooLong
+    );
+
+    let quota_account = &ctx.accounts.quota_account;
+    let role_assignment = ctx.accounts.role_assignment.as_deref();
+    authorize(
+        quota_account,
+        quota_account.key(),
+        ctx.accounts.authority.key(),
+        role_assignment,
+        Action::FlagShipment,
+    )?;
+
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let usage_record = &mut ctx.accounts.usage_record;
+    usage_record.flagged_by = Some(ctx.accounts.authority.key());
+    usage_record.flag_reason = reason.clone();
+    usage_record.flagged_at = current_time;
+
+    // Emit event
+    emit!(ShipmentFlagged {
+        concession_id: usage_record.concession_id.clone(),
+        shipment_id: usage_record.shipment_id.clone(),
+        flagged_by: ctx.accounts.authority.key(),
+        role: role_assignment.map(|assignment| assignment.role),
+        reason,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct GrantRole<'info> {
+    #[account(
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = RoleAssignment::LEN,
+        seeds = [b"role", quota_account.key().as_ref(), member.key().as_ref()],
+        bump
+    )]
+    pub role_assignment: Account<'info, RoleAssignment>,
+
+    /// CHECK: Key receiving the role
+    pub member: AccountInfo<'info>,
+
+    /// Holder (dispatch clerks only) or regulator of the quota
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<GrantRole>, role: Role) -> Result<()> {
+    let quota_account = &ctx.accounts.quota_account;
+    authorize(
+        quota_account,
+        quota_account.key(),
+        ctx.accounts.authority.key(),
+        None,
+        Action::GrantRole(role),
+    )?;
+
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let role_assignment = &mut ctx.accounts.role_assignment;
+    role_assignment.quota_account = quota_account.key();
+    role_assignment.member = ctx.accounts.member.key();
+    role_assignment.role = role;
+    role_assignment.granted_by = ctx.accounts.authority.key();
+    role_assignment.granted_at = current_time;
+    role_assignment.bump = ctx.bumps.role_assignment;
+    role_assignment.payer = ctx.accounts.authority.key();
+
+    // Emit event
+    emit!(RoleGranted {
+        concession_id: quota_account.concession_id.clone(),
+        member: role_assignment.member,
+        role,
+        granted_by: role_assignment.granted_by,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::program::Quotamanagement;
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = ProgramConfig::LEN,
+        seeds = [b"config"],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ QuotaError::UnauthorizedAdmin
+    )]
+    pub program: Program<'info, Quotamanagement>,
+
+    /// Only the program's upgrade authority may claim the admin role
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ QuotaError::UnauthorizedAdmin
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeConfig>) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    program_config.admin = ctx.accounts.admin.key();
+    program_config.regulator_count = 0;
+    program_config.cancellation_window = ProgramConfig::DEFAULT_CANCELLATION_WINDOW;
+    program_config.crank_reward = 0;
+    program_config.created_at = current_time;
+    program_config.updated_at = current_time;
+    program_config.bump = ctx.bumps.program_config;
+
+    // Emit event
+    emit!(ProgramConfigInitialized {
+        admin: program_config.admin,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
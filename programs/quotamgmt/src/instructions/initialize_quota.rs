@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::*;
+use super::close_quota_account::is_closed_account;
 
 #[derive(Accounts)]
 #[instruction(concession_id: String)]
@@ -13,12 +14,27 @@ pub struct InitializeQuota<'info> {
         bump
     )]
     pub quota_account: Account<'info, QuotaAccount>,
+
+    /// CHECK: Tombstone left if a quota was closed at this address before;
+    /// read in the handler
+    #[account(
+        seeds = [b"tombstone", quota_account.key().as_ref()],
+        bump
+    )]
+    pub tombstone: UncheckedAccount<'info>,
     
     /// CHECK: Holder of the quota - verified by regulator
     pub holder: AccountInfo<'info>,
     
     #[account(mut)]
     pub regulator: Signer<'info>,
+
+    #[account(
+        seeds = [b"regulator", regulator.key().as_ref()],
+        bump = regulator_record.bump,
+        constraint = regulator_record.is_active() @ QuotaError::RegulatorNotActive
+    )]
+    pub regulator_record: Account<'info, RegulatorRecord>,
     
     pub system_program: Program<'info, System>,
 }
@@ -29,6 +45,8 @@ pub fn handler(
     allocated_quota: u64,
     validity_period: i64,
     quota_type: QuotaType,
+    mining_region: String,
+    environmental_clearance: String,
 ) -> Result<()> {
     // Validation
     require!(allocated_quota > 0, QuotaError::InvalidQuotaAmount);
@@ -40,11 +58,30 @@ pub fn handler(
         concession_id.len() <= QuotaAccount::MAX_CONCESSION_ID_LEN, 
         QuotaError::ConcessionIdTooLong
     );
+    require!(
+        mining_region.len() <= QuotaAccount::MAX_MINING_REGION_LEN,
+        QuotaError::MiningRegionTooLong
+    );
+    require!(
+        environmental_clearance.len() <= QuotaAccount::MAX_ENV_CLEARANCE_LEN,
+        QuotaError::EnvironmentalClearanceTooLong
+    );
+
+    // A quota re-issued at a closed one's address carries on its record
+    // numbering, since the closed quota's records may still be retained
+    let tombstone = if is_closed_account(&ctx.accounts.tombstone) {
+        None
+    } else {
+        Some(QuotaTombstone::try_deserialize(
+            &mut &ctx.accounts.tombstone.try_borrow_data()?[..],
+        )?)
+    };
 
     let quota_account = &mut ctx.accounts.quota_account;
     let current_time = Clock::get()?.unix_timestamp;
 
     // Initialize quota account
+    quota_account.layout_version = QuotaAccount::LAYOUT_VERSION;
     quota_account.concession_id = concession_id.clone();
     quota_account.holder = ctx.accounts.holder.key();
     quota_account.regulator = ctx.accounts.regulator.key();
@@ -54,11 +91,27 @@ pub fn handler(
     quota_account.validity_period = validity_period;
     quota_account.status = QuotaStatus::Active;
     quota_account.quota_type = quota_type.clone();
-    quota_account.mining_region = String::new(); // Can be updated later
-    quota_account.environmental_clearance = String::new(); // Can be updated later
+    quota_account.mining_region = mining_region;
+    quota_account.environmental_clearance = environmental_clearance;
     quota_account.created_at = current_time;
     quota_account.updated_at = current_time;
     quota_account.bump = ctx.bumps.quota_account;
+    quota_account.transfer_count = tombstone.as_ref().map_or(0, |tombstone| tombstone.transfer_count);
+    quota_account.usage_count = tombstone.as_ref().map_or(0, |tombstone| tombstone.usage_count);
+    quota_account.revoked_by = None;
+    quota_account.revoked_at = 0;
+    quota_account.revocation_reason = None;
+    quota_account.open_shipments = 0;
+    quota_account.quality_policy = None; // Set via assign_quality_policy
+    quota_account.grade_allocations = Vec::new(); // Set via set_grade_allocation
+    quota_account.warning_thresholds = QuotaAccount::DEFAULT_WARNING_THRESHOLDS.to_vec();
+    quota_account.highest_threshold_crossed = 0;
+    quota_account.utilization_cap = None; // Set via set_utilization_thresholds
+    quota_account.dispatch_limits = DispatchLimits::default(); // Set via set_dispatch_limits
+    quota_account.dispatch_window = DispatchWindow::default();
+    quota_account.reserved_quota = 0;
+    quota_account.reservation_count = tombstone.as_ref().map_or(0, |tombstone| tombstone.reservation_count);
+    quota_account.open_transfers = 0;
 
     // Emit event
     emit!(QuotaInitialized {
@@ -68,6 +121,7 @@ pub fn handler(
         validity_period,
         quota_type,
         mining_region: quota_account.mining_region.clone(),
+        environmental_clearance: quota_account.environmental_clearance.clone(),
     });
 
     Ok(())
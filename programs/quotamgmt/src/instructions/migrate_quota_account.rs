@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct MigrateQuotaAccount<'info> {
+    /// CHECK: May be in any older layout, so it cannot be loaded as
+    /// `Account<QuotaAccount>`; discriminator, layout and PDA are verified in
+    /// the handler
+    #[account(mut, owner = crate::ID)]
+    pub quota_account: UncheckedAccount<'info>,
+
+    /// Regulator recorded on the account, pays for the extra space
+    #[account(mut)]
+    pub regulator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateQuotaAccount>) -> Result<()> {
+    let quota_info = ctx.accounts.quota_account.to_account_info();
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let (mut quota_account, old_version) = {
+        let data = quota_info.try_borrow_data()?;
+        (
+            QuotaAccount::decode_stored(&data)?,
+            QuotaAccount::stored_layout_version(&data),
+        )
+    };
+    require!(
+        old_version < QuotaAccount::LAYOUT_VERSION,
+        QuotaError::AccountAlreadyMigrated
+    );
+
+    authorize(
+        &quota_account,
+        quota_info.key(),
+        ctx.accounts.regulator.key(),
+        None,
+        Action::MigrateAccount,
+    )?;
+    let expected_address = Pubkey::create_program_address(
+        &[
+            b"quota",
+            quota_account.concession_id.as_bytes(),
+            quota_account.holder.as_ref(),
+            &[quota_account.bump],
+        ],
+        &crate::ID,
+    )
+    .map_err(|_| QuotaError::UnrecognizedAccountLayout)?;
+    require_keys_eq!(
+        expected_address,
+        quota_info.key(),
+        QuotaError::UnrecognizedAccountLayout
+    );
+
+    resize_program_account(
+        &quota_info,
+        &ctx.accounts.regulator,
+        &ctx.accounts.system_program,
+        QuotaAccount::LEN,
+    )?;
+
+    quota_account.updated_at = current_time;
+    let mut data = quota_info.try_borrow_mut_data()?;
+    quota_account.try_serialize(&mut &mut data[..])?;
+
+    emit!(AccountMigrated {
+        account: quota_info.key(),
+        old_layout_version: old_version,
+        new_layout_version: QuotaAccount::LAYOUT_VERSION,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+/// Resize a program-owned account to `new_len`, topping up rent from `payer`
+pub(crate) fn resize_program_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let required_lamports = Rent::get()?.minimum_balance(new_len);
+    let shortfall = required_lamports.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+
+    account.realloc(new_len, true)?;
+
+    Ok(())
+}
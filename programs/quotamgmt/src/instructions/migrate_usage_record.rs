@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use super::close_quota_account::is_closed_account;
+use super::migrate_quota_account::resize_program_account;
+
+#[derive(Accounts)]
+pub struct MigrateUsageRecord<'info> {
+    /// CHECK: May be in any older layout, so it cannot be loaded as
+    /// `Account<UsageRecord>`; discriminator, layout and PDA are verified in
+    /// the handler
+    #[account(mut, owner = crate::ID)]
+    pub usage_record: UncheckedAccount<'info>,
+
+    /// CHECK: Quota the record belongs to, checked against the record and
+    /// deserialized in the handler; may already have been closed
+    #[account(mut)]
+    pub quota_account: UncheckedAccount<'info>,
+
+    /// Holder recorded on the record, pays for the extra space
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateUsageRecord>) -> Result<()> {
+    let usage_info = ctx.accounts.usage_record.to_account_info();
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let (usage_record, old_version) = {
+        let data = usage_info.try_borrow_data()?;
+        (
+            UsageRecord::decode_stored(&data)?,
+            UsageRecord::stored_layout_version(&data),
+        )
+    };
+    require!(
+        old_version < UsageRecord::LAYOUT_VERSION,
+        QuotaError::AccountAlreadyMigrated
+    );
+
+    require_keys_eq!(
+        usage_record.holder,
+        ctx.accounts.holder.key(),
+        QuotaError::UnauthorizedHolder
+    );
+    let expected_address = usage_record.expected_address()?;
+    require_keys_eq!(
+        expected_address,
+        usage_info.key(),
+        QuotaError::UnrecognizedAccountLayout
+    );
+    require_keys_eq!(
+        usage_record.quota_account,
+        ctx.accounts.quota_account.key(),
+        QuotaError::ShipmentQuotaMismatch
+    );
+
+    // Records from before open shipments were counted are reconciled with
+    // the quota's count, which must be migrated first
+    let quota_info = ctx.accounts.quota_account.to_account_info();
+    if old_version < 5 && !is_closed_account(&quota_info) {
+        require!(
+            QuotaAccount::is_current_layout(&quota_info),
+            QuotaError::AccountMigrationRequired
+        );
+        let mut data = quota_info.try_borrow_mut_data()?;
+        let mut quota_account = QuotaAccount::try_deserialize(&mut &data[..])?;
+        quota_account.count_legacy_shipment(&usage_record);
+        quota_account.try_serialize(&mut &mut data[..])?;
+    }
+
+    resize_program_account(
+        &usage_info,
+        &ctx.accounts.holder,
+        &ctx.accounts.system_program,
+        UsageRecord::LEN,
+    )?;
+
+    let mut data = usage_info.try_borrow_mut_data()?;
+    usage_record.try_serialize(&mut &mut data[..])?;
+
+    emit!(AccountMigrated {
+        account: usage_info.key(),
+        old_layout_version: old_version,
+        new_layout_version: UsageRecord::LAYOUT_VERSION,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -1,13 +1,121 @@
+// Every instruction module exposes its entrypoint as `handler`; lib.rs calls
+// them by path, so the clashing glob re-exports are intentional.
+#![allow(ambiguous_glob_reexports)]
+
 pub mod initialize_quota;
 pub mod use_quota;
-pub mod transfer_quota;
+pub mod request_transfer;
+pub mod approve_transfer;
+pub mod reject_transfer;
+pub mod accept_transfer;
+pub mod execute_transfer;
+pub mod cancel_transfer;
 pub mod update_quota;
 pub mod suspend_quota;
 pub mod reactivate_quota;
+pub mod migrate_quota_account;
+pub mod migrate_usage_record;
+pub mod initialize_config;
+pub mod add_regulator;
+pub mod disable_regulator;
+pub mod enable_regulator;
+pub mod remove_regulator;
+pub mod grant_role;
+pub mod revoke_role;
+pub mod flag_shipment;
+pub mod create_delegate;
+pub mod revoke_delegate;
+pub mod update_shipment_details;
+pub mod mark_shipment_in_transit;
+pub mod confirm_delivery;
+pub mod reject_shipment;
+pub mod cancel_shipment;
+pub mod set_cancellation_window;
+pub mod set_crank_reward;
+pub mod expire_quota;
+pub mod revoke_quota;
+pub mod close_quota_account;
+pub mod close_usage_record;
+pub mod close_transfer_record;
+pub mod update_quota_metadata;
+pub mod register_clearance;
+pub mod update_clearance;
+pub mod set_clearance_status;
+pub mod create_quality_policy;
+pub mod update_quality_policy;
+pub mod assign_quality_policy;
+pub mod set_grade_allocation;
+pub mod register_lab;
+pub mod set_lab_status;
+pub mod attach_assay;
+pub mod set_utilization_thresholds;
+pub mod set_dispatch_limits;
+pub mod reserve_quota;
+pub mod release_reservation;
+pub mod consume_reservation;
+pub mod use_quota_batch;
+pub mod dispatch_rake;
+pub mod verify_rake_wagon;
+pub mod close_rake_shipment;
+pub mod close_clearance;
+pub mod close_role_assignment;
+pub mod close_delegate;
 
 pub use initialize_quota::*;
 pub use use_quota::*;
-pub use transfer_quota::*;
+pub use request_transfer::*;
+pub use approve_transfer::*;
+pub use reject_transfer::*;
+pub use accept_transfer::*;
+pub use execute_transfer::*;
+pub use cancel_transfer::*;
 pub use update_quota::*;
 pub use suspend_quota::*;
-pub use reactivate_quota::*;
\ No newline at end of file
+pub use reactivate_quota::*;
+pub use migrate_quota_account::*;
+pub use migrate_usage_record::*;
+pub use initialize_config::*;
+pub use add_regulator::*;
+pub use disable_regulator::*;
+pub use enable_regulator::*;
+pub use remove_regulator::*;
+pub use grant_role::*;
+pub use revoke_role::*;
+pub use flag_shipment::*;
+pub use create_delegate::*;
+pub use revoke_delegate::*;
+pub use update_shipment_details::*;
+pub use mark_shipment_in_transit::*;
+pub use confirm_delivery::*;
+pub use reject_shipment::*;
+pub use cancel_shipment::*;
+pub use set_cancellation_window::*;
+pub use set_crank_reward::*;
+pub use expire_quota::*;
+pub use revoke_quota::*;
+pub use close_quota_account::*;
+pub use close_usage_record::*;
+pub use close_transfer_record::*;
+pub use update_quota_metadata::*;
+pub use register_clearance::*;
+pub use update_clearance::*;
+pub use set_clearance_status::*;
+pub use create_quality_policy::*;
+pub use update_quality_policy::*;
+pub use assign_quality_policy::*;
+pub use set_grade_allocation::*;
+pub use register_lab::*;
+pub use set_lab_status::*;
+pub use attach_assay::*;
+pub use set_utilization_thresholds::*;
+pub use set_dispatch_limits::*;
+pub use reserve_quota::*;
+pub use release_reservation::*;
+pub use consume_reservation::*;
+pub use use_quota_batch::*;
+pub use dispatch_rake::*;
+pub use verify_rake_wagon::*;
+pub use close_rake_shipment::*;
+pub use close_clearance::*;
+pub use close_role_assignment::*;
+pub use close_delegate::*;
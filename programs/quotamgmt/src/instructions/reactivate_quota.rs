@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::access::*;
 use crate::state::*;
 use crate::errors::*;
 
@@ -8,7 +9,7 @@ pub struct ReactivateQuota<'info> {
         mut,
         seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
         bump = quota_account.bump,
-        has_one = regulator
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
     )]
     pub quota_account: Account<'info, QuotaAccount>,
     
@@ -17,6 +18,14 @@ pub struct ReactivateQuota<'info> {
 }
 
 pub fn handler(ctx: Context<ReactivateQuota>) -> Result<()> {
+    authorize(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.regulator.key(),
+        None,
+        Action::ReactivateQuota,
+    )?;
+
     let quota_account = &mut ctx.accounts.quota_account;
     let current_time = Clock::get()?.unix_timestamp;
     
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct RegisterClearance<'info> {
+    #[account(
+        mut,
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    #[account(
+        init,
+        payer = regulator,
+        space = EnvironmentalClearance::LEN,
+        seeds = [b"clearance", quota_account.key().as_ref()],
+        bump
+    )]
+    pub clearance: Account<'info, EnvironmentalClearance>,
+
+    #[account(mut)]
+    pub regulator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<RegisterClearance>,
+    issuing_authority: String,
+    reference_number: String,
+    document_hash: [u8; 32],
+    annual_capacity: u64,
+    valid_from: i64,
+    valid_until: i64,
+) -> Result<()> {
+    authorize(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.regulator.key(),
+        None,
+        Action::ManageClearance,
+    )?;
+
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Validation
+    require!(
+        issuing_authority.len() <= EnvironmentalClearance::MAX_ISSUING_AUTHORITY_LEN,
+        QuotaError::IssuingAuthorityTooLong
+    );
+    require!(
+        reference_number.len() <= QuotaAccount::MAX_ENV_CLEARANCE_LEN,
+        QuotaError::EnvironmentalClearanceTooLong
+    );
+    require!(
+        annual_capacity > 0 && valid_from < valid_until && valid_until > current_time,
+        QuotaError::InvalidClearanceParameters
+    );
+
+    let clearance = &mut ctx.accounts.clearance;
+    clearance.quota_account = ctx.accounts.quota_account.key();
+    clearance.issuing_authority = issuing_authority.clone();
+    clearance.reference_number = reference_number.clone();
+    clearance.document_hash = document_hash;
+    clearance.annual_capacity = annual_capacity;
+    clearance.valid_from = valid_from;
+    clearance.valid_until = valid_until;
+    clearance.status = ClearanceStatus::Active;
+    clearance.current_year = clearance.year_of(current_time);
+    clearance.used_this_year = 0;
+    clearance.registered_by = ctx.accounts.regulator.key();
+    clearance.created_at = current_time;
+    clearance.updated_at = current_time;
+    clearance.bump = ctx.bumps.clearance;
+
+    // Keep the quota's clearance reference in step with the account
+    let quota_account = &mut ctx.accounts.quota_account;
+    quota_account.environmental_clearance = reference_number.clone();
+    quota_account.updated_at = current_time;
+
+    // Emit event
+    emit!(ClearanceRegistered {
+        concession_id: quota_account.concession_id.clone(),
+        issuing_authority,
+        reference_number,
+        document_hash,
+        annual_capacity,
+        valid_from,
+        valid_until,
+        registered_by: ctx.accounts.regulator.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
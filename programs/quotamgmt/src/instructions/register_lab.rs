@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct RegisterLab<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+        has_one = admin @ QuotaError::UnauthorizedAdmin
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = LabRecord::LEN,
+        seeds = [b"lab", lab.key().as_ref()],
+        bump
+    )]
+    pub lab_record: Account<'info, LabRecord>,
+
+    /// CHECK: Signing key of the laboratory being registered
+    pub lab: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterLab>, name: String, accreditation_id: String) -> Result<()> {
+    // Validation
+    require!(name.len() <= LabRecord::MAX_NAME_LEN, QuotaError::LabNameTooLong);
+    require!(
+        accreditation_id.len() <= LabRecord::MAX_ACCREDITATION_ID_LEN,
+        QuotaError::AccreditationIdTooLong
+    );
+
+    let lab_record = &mut ctx.accounts.lab_record;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    lab_record.lab = ctx.accounts.lab.key();
+    lab_record.name = name.clone();
+    lab_record.accreditation_id = accreditation_id.clone();
+    lab_record.status = LabStatus::Active;
+    lab_record.added_by = ctx.accounts.admin.key();
+    lab_record.added_at = current_time;
+    lab_record.updated_at = current_time;
+    lab_record.bump = ctx.bumps.lab_record;
+
+    // Emit event
+    emit!(LabRegistered {
+        lab: lab_record.lab,
+        name,
+        accreditation_id,
+        added_by: ctx.accounts.admin.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
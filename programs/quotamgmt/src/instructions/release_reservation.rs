@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ReleaseReservation<'info> {
+    #[account(
+        mut,
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    #[account(
+        mut,
+        has_one = quota_account @ QuotaError::ReservationQuotaMismatch,
+        has_one = payer @ QuotaError::PayerMismatch,
+        close = payer
+    )]
+    pub reservation: Account<'info, Reservation>,
+
+    /// CHECK: Original payer of the reservation's rent, bound by `has_one`
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"role", quota_account.key().as_ref(), authority.key().as_ref()],
+        bump = role_assignment.bump
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// Whoever made the reservation, the holder, a dispatch clerk or the
+    /// regulator; anyone once the reservation has expired
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ReleaseReservation>) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let reservation = &ctx.accounts.reservation;
+    let expired = reservation.is_expired(current_time);
+
+    // Lapsed holds may be cleared by anyone
+    if !expired && ctx.accounts.authority.key() != reservation.payer {
+        authorize(
+            &ctx.accounts.quota_account,
+            ctx.accounts.quota_account.key(),
+            ctx.accounts.authority.key(),
+            ctx.accounts.role_assignment.as_deref(),
+            Action::ReleaseReservation,
+        )?;
+    }
+
+    // Return the tonnage to the quota
+    let quota_account = &mut ctx.accounts.quota_account;
+    quota_account.release_hold(reservation.amount);
+    quota_account.updated_at = current_time;
+
+    // Released tonnage can bring an exhausted quota back into use while it is still valid
+    if quota_account.status == QuotaStatus::Exhausted
+        && quota_account.available_quota > 0
+        && current_time <= quota_account.validity_period
+    {
+        quota_account.status = QuotaStatus::Active;
+    }
+
+    // Emit event
+    emit!(ReservationReleased {
+        concession_id: quota_account.concession_id.clone(),
+        sequence: reservation.sequence,
+        amount: reservation.amount,
+        expired,
+        released_by: ctx.accounts.authority.key(),
+        available_quota: quota_account.available_quota,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
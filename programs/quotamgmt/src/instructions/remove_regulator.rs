@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct RemoveRegulator<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = program_config.bump,
+        has_one = admin @ QuotaError::UnauthorizedAdmin
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"regulator", regulator_record.regulator.as_ref()],
+        bump = regulator_record.bump
+    )]
+    pub regulator_record: Account<'info, RegulatorRecord>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveRegulator>) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.regulator_count -= 1;
+    program_config.updated_at = current_time;
+
+    // Emit event
+    emit!(RegulatorRemoved {
+        regulator: ctx.accounts.regulator_record.regulator,
+        removed_by: ctx.accounts.admin.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
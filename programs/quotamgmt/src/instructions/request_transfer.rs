@@ -1,22 +1,22 @@
 use anchor_lang::prelude::*;
+use crate::access::*;
 use crate::state::*;
 use crate::errors::*;
 
 #[derive(Accounts)]
-#[instruction(amount: u64, transfer_reason: String)]
-pub struct TransferQuota<'info> {
+pub struct RequestTransfer<'info> {
     #[account(
         mut,
-        has_one = holder,
         seeds = [b"quota", from_quota.concession_id.as_bytes(), from_quota.holder.as_ref()],
-        bump = from_quota.bump
+        bump = from_quota.bump,
+        constraint = QuotaAccount::is_current_layout(from_quota.as_ref()) @ QuotaError::AccountMigrationRequired
     )]
     pub from_quota: Account<'info, QuotaAccount>,
     
     #[account(
-        mut,
         seeds = [b"quota", to_quota.concession_id.as_bytes(), to_quota.holder.as_ref()],
-        bump = to_quota.bump
+        bump = to_quota.bump,
+        constraint = QuotaAccount::is_current_layout(to_quota.as_ref()) @ QuotaError::AccountMigrationRequired
     )]
     pub to_quota: Account<'info, QuotaAccount>,
 
@@ -25,10 +25,9 @@ pub struct TransferQuota<'info> {
         payer = holder,
         space = TransferRecord::LEN,
         seeds = [
-            b"transfer", 
-            from_quota.concession_id.as_bytes(),
-            to_quota.concession_id.as_bytes(),
-            &Clock::get().unwrap().unix_timestamp.to_le_bytes()
+            b"transfer",
+            from_quota.key().as_ref(),
+            &from_quota.transfer_count.to_le_bytes()
         ],
         bump
     )]
@@ -41,12 +40,21 @@ pub struct TransferQuota<'info> {
 }
 
 pub fn handler(
-    ctx: Context<TransferQuota>,
+    ctx: Context<RequestTransfer>,
     amount: u64,
     transfer_reason: String,
+    transfer_type: TransferType,
 ) -> Result<()> {
+    authorize(
+        &ctx.accounts.from_quota,
+        ctx.accounts.from_quota.key(),
+        ctx.accounts.holder.key(),
+        None,
+        Action::TransferQuota,
+    )?;
+
     let from_quota = &mut ctx.accounts.from_quota;
-    let to_quota = &mut ctx.accounts.to_quota;
+    let to_quota = &ctx.accounts.to_quota;
     let current_time = Clock::get()?.unix_timestamp;
 
     // Validation
@@ -67,40 +75,41 @@ pub fn handler(
     require!(current_time <= from_quota.validity_period, QuotaError::QuotaExpired);
     require!(current_time <= to_quota.validity_period, QuotaError::QuotaExpired);
 
-    // Update quotas
-    from_quota.available_quota -= amount;
-    from_quota.allocated_quota -= amount;
+    let sequence = from_quota.transfer_count;
+    from_quota.transfer_count += 1;
+    from_quota.open_transfers += 1;
     from_quota.updated_at = current_time;
 
-    to_quota.available_quota += amount;
-    to_quota.allocated_quota += amount;
-    to_quota.updated_at = current_time;
-
-    // Update status if quota is exhausted
-    if from_quota.available_quota == 0 {
-        from_quota.status = QuotaStatus::Exhausted;
-    }
-
-    // Create transfer record
+    // Create transfer record; tonnage only moves on execution
     let transfer_record = &mut ctx.accounts.transfer_record;
     transfer_record.from_concession = from_quota.concession_id.clone();
     transfer_record.to_concession = to_quota.concession_id.clone();
     transfer_record.amount = amount;
     transfer_record.timestamp = current_time;
-    transfer_record.authorized_by = ctx.accounts.holder.key();
+    transfer_record.authorized_by = Pubkey::default();
     transfer_record.transfer_reason = transfer_reason;
-    transfer_record.transfer_type = TransferType::Planned; // Default type
+    transfer_record.transfer_type = transfer_type.clone();
     transfer_record.bump = ctx.bumps.transfer_record;
+    transfer_record.from_quota = from_quota.key();
+    transfer_record.to_quota = to_quota.key();
+    transfer_record.requested_by = ctx.accounts.holder.key();
+    transfer_record.status = TransferStatus::Requested;
+    transfer_record.decided_at = 0;
+    transfer_record.accepted_at = 0;
+    transfer_record.executed_at = 0;
+    transfer_record.rejection_reason = String::new();
+    transfer_record.sequence = sequence;
 
     // Emit event
-    emit!(QuotaTransferred {
+    emit!(TransferRequested {
         from_concession: from_quota.concession_id.clone(),
         to_concession: to_quota.concession_id.clone(),
+        sequence,
         amount,
-        transfer_type: TransferType::Planned,
-        authorized_by: ctx.accounts.holder.key(),
+        transfer_type,
+        requested_by: ctx.accounts.holder.key(),
         timestamp: current_time,
     });
 
     Ok(())
-}
\ No newline at end of file
+}
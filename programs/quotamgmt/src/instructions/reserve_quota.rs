@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ReserveQuota<'info> {
+    #[account(
+        mut,
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Reservation::LEN,
+        seeds = [
+            b"reservation",
+            quota_account.key().as_ref(),
+            &quota_account.reservation_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub reservation: Account<'info, Reservation>,
+
+    #[account(
+        seeds = [b"role", quota_account.key().as_ref(), authority.key().as_ref()],
+        bump = role_assignment.bump
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// Holder or dispatch clerk of the quota, pays for the reservation
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<ReserveQuota>,
+    amount: u64,
+    reference: String,
+    expires_at: i64,
+) -> Result<()> {
+    authorize(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.authority.key(),
+        ctx.accounts.role_assignment.as_deref(),
+        Action::ReserveQuota,
+    )?;
+
+    let quota_account = &mut ctx.accounts.quota_account;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Validation
+    require!(quota_account.status == QuotaStatus::Active, QuotaError::QuotaNotActive);
+    require!(current_time <= quota_account.validity_period, QuotaError::QuotaExpired);
+    require!(amount > 0, QuotaError::InvalidUsageAmount);
+    require!(
+        reference.len() <= Reservation::MAX_REFERENCE_LEN,
+        QuotaError::ReservationReferenceTooLong
+    );
+    require!(
+        expires_at > current_time
+            && expires_at <= current_time + Reservation::MAX_RESERVATION_PERIOD
+            && expires_at <= quota_account.validity_period,
+        QuotaError::InvalidReservationExpiry
+    );
+
+    // Hold the tonnage
+    quota_account.hold(amount)?;
+    let sequence = quota_account.reservation_count;
+    quota_account.reservation_count += 1;
+    quota_account.updated_at = current_time;
+
+    let reservation = &mut ctx.accounts.reservation;
+    reservation.quota_account = quota_account.key();
+    reservation.sequence = sequence;
+    reservation.amount = amount;
+    reservation.reference = reference.clone();
+    reservation.payer = ctx.accounts.authority.key();
+    reservation.expires_at = expires_at;
+    reservation.created_at = current_time;
+    reservation.bump = ctx.bumps.reservation;
+
+    // Emit event
+    emit!(QuotaReserved {
+        concession_id: quota_account.concession_id.clone(),
+        sequence,
+        amount,
+        reference,
+        reserved_by: ctx.accounts.authority.key(),
+        expires_at,
+        reserved_quota: quota_account.reserved_quota,
+        available_quota: quota_account.available_quota,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct RevokeDelegate<'info> {
+    #[account(
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    #[account(
+        mut,
+        close = holder,
+        seeds = [b"delegate", quota_account.key().as_ref(), delegate.delegate.as_ref()],
+        bump = delegate.bump
+    )]
+    pub delegate: Account<'info, Delegate>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RevokeDelegate>) -> Result<()> {
+    authorize(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.holder.key(),
+        None,
+        Action::ManageDelegates,
+    )?;
+
+    let delegate = &ctx.accounts.delegate;
+
+    // Emit event
+    emit!(DelegateRevoked {
+        concession_id: ctx.accounts.quota_account.concession_id.clone(),
+        delegate: delegate.delegate,
+        used_tonnage: delegate.used_tonnage,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct RevokeQuota<'info> {
+    #[account(
+        mut,
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    pub regulator: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<RevokeQuota>,
+    reason_code: RevocationReason,
+    details: String,
+) -> Result<()> {
+    authorize(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.regulator.key(),
+        None,
+        Action::RevokeQuota,
+    )?;
+
+    require!(details.len() <= 200, QuotaError::ReasonTooLong);
+
+    let quota_account = &mut ctx.accounts.quota_account;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Remaining tonnage stays on the record but can no longer be used,
+    // transferred or refunded into
+    let old_status = quota_account.status.clone();
+    quota_account.status = QuotaStatus::Revoked;
+    quota_account.revoked_by = Some(ctx.accounts.regulator.key());
+    quota_account.revoked_at = current_time;
+    quota_account.revocation_reason = Some(reason_code.clone());
+    quota_account.updated_at = current_time;
+
+    // Emit event
+    emit!(QuotaStatusUpdated {
+        concession_id: quota_account.concession_id.clone(),
+        old_status,
+        new_status: QuotaStatus::Revoked,
+        updated_by: ctx.accounts.regulator.key(),
+        reason: details.clone(),
+        timestamp: current_time,
+    });
+
+    emit!(QuotaRevoked {
+        concession_id: quota_account.concession_id.clone(),
+        reason_code,
+        details,
+        frozen_quota: quota_account.available_quota,
+        revoked_by: ctx.accounts.regulator.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
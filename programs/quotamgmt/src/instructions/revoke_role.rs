@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    #[account(
+        mut,
+        has_one = payer @ QuotaError::PayerMismatch,
+        close = payer,
+        seeds = [b"role", quota_account.key().as_ref(), role_assignment.member.as_ref()],
+        bump = role_assignment.bump
+    )]
+    pub role_assignment: Account<'info, RoleAssignment>,
+
+    /// CHECK: Original payer of the assignment's rent, bound by `has_one`
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// Holder (dispatch clerks only) or regulator of the quota
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RevokeRole>) -> Result<()> {
+    let quota_account = &ctx.accounts.quota_account;
+    let role_assignment = &ctx.accounts.role_assignment;
+
+    // Whoever may grant a role may also take it away
+    authorize(
+        quota_account,
+        quota_account.key(),
+        ctx.accounts.authority.key(),
+        None,
+        Action::GrantRole(role_assignment.role),
+    )?;
+
+    // Emit event
+    emit!(RoleRevoked {
+        concession_id: quota_account.concession_id.clone(),
+        member: role_assignment.member,
+        role: role_assignment.role,
+        revoked_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
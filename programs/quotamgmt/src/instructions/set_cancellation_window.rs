@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetCancellationWindow<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = program_config.bump,
+        has_one = admin @ QuotaError::UnauthorizedAdmin
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetCancellationWindow>, window_seconds: i64) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Validation
+    require!(window_seconds >= 0, QuotaError::InvalidCancellationWindow);
+
+    let old_window = program_config.cancellation_window;
+    program_config.cancellation_window = window_seconds;
+    program_config.updated_at = current_time;
+
+    // Emit event
+    emit!(CancellationWindowUpdated {
+        old_window,
+        new_window: window_seconds,
+        updated_by: ctx.accounts.admin.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetClearanceStatus<'info> {
+    #[account(
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"clearance", quota_account.key().as_ref()],
+        bump = clearance.bump
+    )]
+    pub clearance: Account<'info, EnvironmentalClearance>,
+
+    pub regulator: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetClearanceStatus>,
+    status: ClearanceStatus,
+    reason: String,
+) -> Result<()> {
+    authorize(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.regulator.key(),
+        None,
+        Action::ManageClearance,
+    )?;
+
+    require!(reason.len() <= 200, QuotaError::ReasonTooLong);
+
+    let clearance = &mut ctx.accounts.clearance;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let old_status = clearance.status.clone();
+    clearance.status = status.clone();
+    clearance.updated_at = current_time;
+
+    // Emit event
+    emit!(ClearanceStatusUpdated {
+        concession_id: ctx.accounts.quota_account.concession_id.clone(),
+        old_status,
+        new_status: status,
+        reason,
+        updated_by: ctx.accounts.regulator.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetCrankReward<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = program_config.bump,
+        has_one = admin @ QuotaError::UnauthorizedAdmin
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetCrankReward>, reward_lamports: u64) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let old_reward = program_config.crank_reward;
+    program_config.crank_reward = reward_lamports;
+    program_config.updated_at = current_time;
+
+    // Emit event
+    emit!(CrankRewardUpdated {
+        old_reward,
+        new_reward: reward_lamports,
+        updated_by: ctx.accounts.admin.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetDispatchLimits<'info> {
+    #[account(
+        mut,
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    pub regulator: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetDispatchLimits>, dispatch_limits: DispatchLimits) -> Result<()> {
+    authorize(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.regulator.key(),
+        None,
+        Action::UpdateQuota,
+    )?;
+
+    // Validation
+    require!(
+        dispatch_limits.max_daily_tonnage != Some(0)
+            && dispatch_limits.max_weekly_tonnage != Some(0)
+            && dispatch_limits.max_daily_shipments != Some(0),
+        QuotaError::InvalidDispatchLimits
+    );
+
+    let quota_account = &mut ctx.accounts.quota_account;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    quota_account.dispatch_limits = dispatch_limits.clone();
+    quota_account.updated_at = current_time;
+
+    // Emit event
+    emit!(DispatchLimitsUpdated {
+        concession_id: quota_account.concession_id.clone(),
+        dispatch_limits,
+        updated_by: ctx.accounts.regulator.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
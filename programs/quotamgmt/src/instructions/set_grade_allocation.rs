@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetGradeAllocation<'info> {
+    #[account(
+        mut,
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    pub regulator: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetGradeAllocation>, grade: CoalGrade, allocated: u64) -> Result<()> {
+    authorize(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.regulator.key(),
+        None,
+        Action::UpdateQuota,
+    )?;
+
+    let quota_account = &mut ctx.accounts.quota_account;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let existing = quota_account
+        .grade_allocations
+        .iter()
+        .position(|allocation| allocation.grade == grade);
+    let (old_allocated, used) = existing
+        .map(|index| {
+            let allocation = &quota_account.grade_allocations[index];
+            (allocation.allocated, allocation.used)
+        })
+        .unwrap_or((0, 0));
+
+    // Validation
+    require!(allocated >= used, QuotaError::InvalidQuotaAmount);
+    let other_allocated: u64 = quota_account
+        .grade_allocations
+        .iter()
+        .filter(|allocation| allocation.grade != grade)
+        .map(|allocation| allocation.allocated)
+        .sum();
+    require!(
+        other_allocated + allocated <= quota_account.allocated_quota,
+        QuotaError::GradeAllocationExceedsQuota
+    );
+
+    let allocation = GradeAllocation {
+        grade: grade.clone(),
+        allocated,
+        used,
+        available: allocated - used,
+    };
+    let available = allocation.available;
+    match existing {
+        Some(index) => quota_account.grade_allocations[index] = allocation,
+        None => quota_account.grade_allocations.push(allocation),
+    }
+    quota_account.updated_at = current_time;
+
+    // Emit event
+    emit!(GradeAllocationUpdated {
+        concession_id: quota_account.concession_id.clone(),
+        grade,
+        old_allocated,
+        new_allocated: allocated,
+        used,
+        available,
+        updated_by: ctx.accounts.regulator.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
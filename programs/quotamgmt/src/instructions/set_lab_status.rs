@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetLabStatus<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+        has_one = admin @ QuotaError::UnauthorizedAdmin
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"lab", lab_record.lab.as_ref()],
+        bump = lab_record.bump
+    )]
+    pub lab_record: Account<'info, LabRecord>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetLabStatus>, status: LabStatus) -> Result<()> {
+    let lab_record = &mut ctx.accounts.lab_record;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let old_status = lab_record.status.clone();
+    lab_record.status = status.clone();
+    lab_record.updated_at = current_time;
+
+    // Emit event
+    emit!(LabStatusUpdated {
+        lab: lab_record.lab,
+        old_status,
+        new_status: status,
+        updated_by: ctx.accounts.admin.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetUtilizationThresholds<'info> {
+    #[account(
+        mut,
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    pub regulator: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetUtilizationThresholds>,
+    warning_thresholds: Vec<u8>,
+    utilization_cap: Option<u8>,
+) -> Result<()> {
+    authorize(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.regulator.key(),
+        None,
+        Action::UpdateQuota,
+    )?;
+
+    // Validation
+    require!(
+        warning_thresholds.len() <= QuotaAccount::MAX_WARNING_THRESHOLDS,
+        QuotaError::InvalidUtilizationThresholds
+    );
+    require!(
+        warning_thresholds.first().is_none_or(|&threshold| threshold > 0)
+            && warning_thresholds.last().is_none_or(|&threshold| threshold <= 100)
+            && warning_thresholds.windows(2).all(|pair| pair[0] < pair[1]),
+        QuotaError::InvalidUtilizationThresholds
+    );
+    require!(
+        utilization_cap.is_none_or(|cap| cap > 0 && cap < 100),
+        QuotaError::InvalidUtilizationThresholds
+    );
+
+    let quota_account = &mut ctx.accounts.quota_account;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    quota_account.warning_thresholds = warning_thresholds.clone();
+    quota_account.utilization_cap = utilization_cap;
+    quota_account.updated_at = current_time;
+
+    // Emit event
+    emit!(UtilizationThresholdsUpdated {
+        concession_id: quota_account.concession_id.clone(),
+        warning_thresholds,
+        utilization_cap,
+        updated_by: ctx.accounts.regulator.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct UpdateClearance<'info> {
+    #[account(
+        mut,
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"clearance", quota_account.key().as_ref()],
+        bump = clearance.bump
+    )]
+    pub clearance: Account<'info, EnvironmentalClearance>,
+
+    pub regulator: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateClearance>,
+    reference_number: Option<String>,
+    document_hash: Option<[u8; 32]>,
+    annual_capacity: Option<u64>,
+    valid_until: Option<i64>,
+) -> Result<()> {
+    authorize(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.regulator.key(),
+        None,
+        Action::ManageClearance,
+    )?;
+
+    let clearance = &mut ctx.accounts.clearance;
+    let quota_account = &mut ctx.accounts.quota_account;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    if let Some(reference) = reference_number {
+        require!(
+            reference.len() <= QuotaAccount::MAX_ENV_CLEARANCE_LEN,
+            QuotaError::EnvironmentalClearanceTooLong
+        );
+        quota_account.environmental_clearance = reference.clone();
+        quota_account.updated_at = current_time;
+        clearance.reference_number = reference;
+    }
+
+    if let Some(hash) = document_hash {
+        clearance.document_hash = hash;
+    }
+
+    if let Some(capacity) = annual_capacity {
+        require!(capacity > 0, QuotaError::InvalidClearanceParameters);
+        clearance.annual_capacity = capacity;
+    }
+
+    if let Some(until) = valid_until {
+        require!(
+            until > clearance.valid_from && until > current_time,
+            QuotaError::InvalidClearanceParameters
+        );
+        clearance.valid_until = until;
+    }
+
+    clearance.updated_at = current_time;
+
+    // Emit event
+    emit!(ClearanceUpdated {
+        concession_id: quota_account.concession_id.clone(),
+        reference_number: clearance.reference_number.clone(),
+        document_hash: clearance.document_hash,
+        annual_capacity: clearance.annual_capacity,
+        valid_until: clearance.valid_until,
+        updated_by: ctx.accounts.regulator.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
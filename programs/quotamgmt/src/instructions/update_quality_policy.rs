@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct UpdateQualityPolicy<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"quality_policy",
+            quality_policy.regulator.as_ref(),
+            quality_policy.policy_id.as_bytes()
+        ],
+        bump = quality_policy.bump,
+        has_one = regulator @ QuotaError::UnauthorizedRegulator
+    )]
+    pub quality_policy: Account<'info, QualityPolicy>,
+
+    pub regulator: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UpdateQualityPolicy>, bounds: QualityBounds) -> Result<()> {
+    // Validation
+    require!(bounds.is_consistent(), QuotaError::InvalidQualityParameters);
+
+    let quality_policy = &mut ctx.accounts.quality_policy;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let old_bounds = quality_policy.bounds.clone();
+    quality_policy.bounds = bounds.clone();
+    quality_policy.updated_at = current_time;
+
+    // Emit event
+    emit!(QualityPolicyUpdated {
+        regulator: quality_policy.regulator,
+        policy_id: quality_policy.policy_id.clone(),
+        old_bounds,
+        new_bounds: bounds,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
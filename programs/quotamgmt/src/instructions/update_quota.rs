@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::access::*;
 use crate::state::*;
 use crate::errors::*;
 
@@ -8,7 +9,7 @@ pub struct UpdateQuota<'info> {
         mut,
         seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
         bump = quota_account.bump,
-        has_one = regulator
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
     )]
     pub quota_account: Account<'info, QuotaAccount>,
     
@@ -23,6 +24,14 @@ pub fn handler(
     status: Option<QuotaStatus>,
     update_reason: String,
 ) -> Result<()> {
+    authorize(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.regulator.key(),
+        None,
+        Action::UpdateQuota,
+    )?;
+
     let quota_account = &mut ctx.accounts.quota_account;
     let current_time = Clock::get()?.unix_timestamp;
 
@@ -45,22 +54,24 @@ pub fn handler(
     // Update allocated quota
     if let Some(new_quota) = new_allocated_quota {
         require!(new_quota > 0, QuotaError::InvalidQuotaAmount);
-        
-        let difference = if new_quota > quota_account.allocated_quota {
-            new_quota - quota_account.allocated_quota
-        } else {
-            quota_account.allocated_quota - new_quota
-        };
+        require!(
+            new_quota >= quota_account.grade_allocated_total(),
+            QuotaError::GradeAllocationExceedsQuota
+        );
 
         // Update allocated and available quota
         quota_account.allocated_quota = new_quota;
         
-        // Adjust available quota proportionally
-        if new_quota >= quota_account.used_quota {
-            quota_account.available_quota = new_quota - quota_account.used_quota;
+        // Adjust available quota proportionally, keeping reservations on hold
+        let committed = quota_account.used_quota + quota_account.reserved_quota;
+        if new_quota >= committed {
+            quota_account.available_quota = new_quota - committed;
         } else {
-            // If new quota is less than used quota, set available to 0
+            // If new quota is less than used and reserved quota, set available to 0
             quota_account.available_quota = 0;
+        }
+        // Tonnage still on hold may yet be dispatched
+        if quota_account.available_quota == 0 && quota_account.reserved_quota == 0 {
             quota_account.status = QuotaStatus::Exhausted;
         }
     }
@@ -81,6 +92,11 @@ pub fn handler(
 
     // Update status
     if let Some(new_status) = status {
+        // Revocation needs a reason code and is recorded by revoke_quota
+        require!(
+            new_status != QuotaStatus::Revoked,
+            QuotaError::RevocationRequiresReasonCode
+        );
         quota_account.status = new_status;
     }
 
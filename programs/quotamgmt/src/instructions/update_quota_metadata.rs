@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct UpdateQuotaMetadata<'info> {
+    #[account(
+        mut,
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    pub regulator: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateQuotaMetadata>,
+    mining_region: Option<String>,
+    environmental_clearance: Option<String>,
+) -> Result<()> {
+    authorize(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.regulator.key(),
+        None,
+        Action::UpdateQuota,
+    )?;
+
+    let quota_account = &mut ctx.accounts.quota_account;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Store old values for event
+    let old_mining_region = quota_account.mining_region.clone();
+    let old_environmental_clearance = quota_account.environmental_clearance.clone();
+
+    if let Some(region) = mining_region {
+        require!(
+            region.len() <= QuotaAccount::MAX_MINING_REGION_LEN,
+            QuotaError::MiningRegionTooLong
+        );
+        quota_account.mining_region = region;
+    }
+
+    if let Some(clearance) = environmental_clearance {
+        require!(
+            clearance.len() <= QuotaAccount::MAX_ENV_CLEARANCE_LEN,
+            QuotaError::EnvironmentalClearanceTooLong
+        );
+        quota_account.environmental_clearance = clearance;
+    }
+
+    quota_account.updated_at = current_time;
+
+    // Emit event
+    emit!(QuotaMetadataUpdated {
+        concession_id: quota_account.concession_id.clone(),
+        old_mining_region,
+        new_mining_region: quota_account.mining_region.clone(),
+        old_environmental_clearance,
+        new_environmental_clearance: quota_account.environmental_clearance.clone(),
+        updated_by: ctx.accounts.regulator.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
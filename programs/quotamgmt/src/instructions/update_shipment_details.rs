@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct UpdateShipmentDetails<'info> {
+    #[account(
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    #[account(
+        mut,
+        has_one = quota_account @ QuotaError::ShipmentQuotaMismatch,
+        constraint = UsageRecord::is_current_layout(usage_record.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub usage_record: Account<'info, UsageRecord>,
+
+    #[account(
+        seeds = [b"delegate", quota_account.key().as_ref(), authority.key().as_ref()],
+        bump = delegate.bump
+    )]
+    pub delegate: Option<Account<'info, Delegate>>,
+
+    /// Holder or a delegate of the quota
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateShipmentDetails>,
+    source_location: Option<String>,
+    destination_location: Option<String>,
+    transport_details: Option<String>,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+
+    authorize_with_delegate(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.authority.key(),
+        None,
+        ctx.accounts.delegate.as_deref(),
+        current_time,
+        Action::UpdateShipment,
+    )?;
+
+    let usage_record = &mut ctx.accounts.usage_record;
+
+    // Validation
+    require!(
+        !usage_record.status.is_final(),
+        QuotaError::ShipmentAlreadyFinalised
+    );
+    require!(
+        current_time <= usage_record.timestamp + UsageRecord::DETAILS_EDIT_WINDOW,
+        QuotaError::ShipmentEditWindowClosed
+    );
+    for location in [&source_location, &destination_location].into_iter().flatten() {
+        require!(
+            location.len() <= UsageRecord::MAX_LOCATION_LEN,
+            QuotaError::LocationTooLong
+        );
+    }
+    if let Some(details) = &transport_details {
+        require!(
+            details.len() <= UsageRecord::MAX_TRANSPORT_DETAILS_LEN,
+            QuotaError::TransportDetailsTooLong
+        );
+    }
+
+    // Update shipment details
+    if let Some(location) = source_location {
+        usage_record.source_location = location;
+    }
+    if let Some(location) = destination_location {
+        usage_record.destination_location = location;
+    }
+    if let Some(details) = transport_details {
+        usage_record.transport_details = details;
+    }
+
+    // Emit event
+    emit!(ShipmentDetailsUpdated {
+        concession_id: usage_record.concession_id.clone(),
+        shipment_id: usage_record.shipment_id.clone(),
+        sequence: usage_record.sequence,
+        source_location: usage_record.source_location.clone(),
+        destination_location: usage_record.destination_location.clone(),
+        transport_details: usage_record.transport_details.clone(),
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -1,29 +1,60 @@
 use anchor_lang::prelude::*;
+use crate::access::*;
 use crate::state::*;
 use crate::errors::*;
+use super::close_quota_account::is_closed_account;
 
 #[derive(Accounts)]
-#[instruction(amount: u64, shipment_id: String)]
 pub struct UseQuota<'info> {
     #[account(
         mut,
         seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
         bump = quota_account.bump,
-        has_one = holder
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
     )]
     pub quota_account: Account<'info, QuotaAccount>,
 
     #[account(
         init,
-        payer = holder,
+        payer = authority,
         space = UsageRecord::LEN,
-        seeds = [b"usage", shipment_id.as_bytes(), holder.key().as_ref()],
+        seeds = [
+            b"usage",
+            quota_account.key().as_ref(),
+            &quota_account.usage_count.to_le_bytes()
+        ],
         bump
     )]
     pub usage_record: Account<'info, UsageRecord>,
+
+    #[account(
+        seeds = [b"role", quota_account.key().as_ref(), authority.key().as_ref()],
+        bump = role_assignment.bump
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"delegate", quota_account.key().as_ref(), authority.key().as_ref()],
+        bump = delegate.bump
+    )]
+    pub delegate: Option<Account<'info, Delegate>>,
+
+    /// CHECK: Environmental clearance of the quota, charged in the handler
+    /// once one has been registered at this address
+    #[account(
+        mut,
+        seeds = [b"clearance", quota_account.key().as_ref()],
+        bump
+    )]
+    pub clearance: UncheckedAccount<'info>,
+
+    /// Quality policy assigned to the quota, if any
+    pub quality_policy: Option<Account<'info, QualityPolicy>>,
     
+    /// Holder, dispatch clerk or delegate of the quota, pays for the usage record
     #[account(mut)]
-    pub holder: Signer<'info>,
+    pub authority: Signer<'info>,
     
     pub system_program: Program<'info, System>,
 }
@@ -34,51 +65,167 @@ pub fn handler(
     shipment_id: String,
     quality_params: QualityParameters,
 ) -> Result<()> {
-    let quota_account = &mut ctx.accounts.quota_account;
     let current_time = Clock::get()?.unix_timestamp;
-    
+
+    // Delegates are bound to this quota and signer by their PDA seeds and
+    // draw down their own allowance; everyone else goes through the roles
+    if let Some(delegate) = ctx.accounts.delegate.as_mut() {
+        delegate.consume(amount, current_time)?;
+    } else {
+        authorize(
+            &ctx.accounts.quota_account,
+            ctx.accounts.quota_account.key(),
+            ctx.accounts.authority.key(),
+            ctx.accounts.role_assignment.as_deref(),
+            Action::UseQuota,
+        )?;
+    }
+
+    let quota_key = ctx.accounts.quota_account.key();
+    record_usage(
+        quota_key,
+        &mut ctx.accounts.quota_account,
+        &mut ctx.accounts.usage_record,
+        ctx.bumps.usage_record,
+        &ctx.accounts.clearance,
+        ctx.accounts.quality_policy.as_ref(),
+        Shipment {
+            amount,
+            shipment_id,
+            quality_params,
+            payer: ctx.accounts.authority.key(),
+            delegate: ctx.accounts.delegate.as_ref().map(|delegate| delegate.key()),
+        },
+    )
+}
+
+/// Shipment being dispatched against a quota
+pub(crate) struct Shipment {
+    pub amount: u64,
+    pub shipment_id: String,
+    pub quality_params: QualityParameters,
+    /// Account that paid for the usage record and reclaims its rent
+    pub payer: Pubkey,
+    /// Delegate account whose allowance the shipment was charged to
+    pub delegate: Option<Pubkey>,
+}
+
+/// Charge a shipment to the quota and fill in its usage record. Every path
+/// that dispatches coal goes through here so the same checks apply.
+pub(crate) fn record_usage(
+    quota_key: Pubkey,
+    quota_account: &mut QuotaAccount,
+    usage_record: &mut UsageRecord,
+    usage_bump: u8,
+    clearance: &AccountInfo,
+    quality_policy: Option<&Account<QualityPolicy>>,
+    shipment: Shipment,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let Shipment { amount, shipment_id, quality_params, payer, delegate } = shipment;
+
     // Validation
     require!(quota_account.status == QuotaStatus::Active, QuotaError::QuotaNotActive);
     require!(current_time <= quota_account.validity_period, QuotaError::QuotaExpired);
     require!(amount > 0, QuotaError::InvalidUsageAmount);
     require!(quota_account.available_quota >= amount, QuotaError::InsufficientQuota);
+    require!(
+        quota_account.within_utilization_cap(amount),
+        QuotaError::UtilizationThresholdExceeded
+    );
     require!(
         shipment_id.len() <= UsageRecord::MAX_SHIPMENT_ID_LEN, 
         QuotaError::ShipmentIdTooLong
     );
     
-    // Validate quality parameters
-    validate_quality_parameters(&quality_params)?;
+    // Validate quality parameters; quotas without a policy keep the default bounds
+    require!(
+        quota_account.quality_policy == quality_policy.map(|policy| policy.key()),
+        QuotaError::QualityPolicyMismatch
+    );
+    let bounds = quality_policy
+        .map(|policy| policy.bounds.clone())
+        .unwrap_or_default();
+    validate_quality_parameters(&quality_params, &bounds)?;
+
+    // Hold the concession to the regulator's daily and weekly pace
+    quota_account.record_dispatch(amount, current_time)?;
+
+    // Every dispatch counts against the quota's environmental clearance, once
+    // one has been registered
+    if !is_closed_account(clearance) {
+        let mut data = clearance.try_borrow_mut_data()?;
+        let mut environmental_clearance = EnvironmentalClearance::try_deserialize(&mut &data[..])?;
+        environmental_clearance.consume(amount, current_time)?;
+        environmental_clearance.try_serialize(&mut &mut data[..])?;
+    }
+
+    // Charge the grade's sub-quota where the lease splits tonnage by grade
+    let remaining_grade_quota = quota_account.debit_grade(&quality_params.coal_grade, amount)?;
 
     // Update quota account
+    let sequence = quota_account.usage_count;
     quota_account.used_quota += amount;
     quota_account.available_quota -= amount;
+    quota_account.usage_count += 1;
+    quota_account.open_shipments += 1;
     quota_account.updated_at = current_time;
 
-    // Check if quota is now exhausted
-    if quota_account.available_quota == 0 {
+    // Check if quota is now exhausted; tonnage still on hold may yet be dispatched
+    if quota_account.available_quota == 0 && quota_account.reserved_quota == 0 {
         quota_account.status = QuotaStatus::Exhausted;
     }
 
+    // Warn the first time utilization reaches each threshold
+    for threshold in quota_account.cross_warning_thresholds() {
+        emit!(UtilizationThresholdCrossed {
+            concession_id: quota_account.concession_id.clone(),
+            threshold,
+            utilization: quota_account.utilization_percentage(),
+            used_quota: quota_account.used_quota,
+            allocated_quota: quota_account.allocated_quota,
+            timestamp: current_time,
+        });
+    }
+
     // Record usage
-    let usage_record = &mut ctx.accounts.usage_record;
+    usage_record.layout_version = UsageRecord::LAYOUT_VERSION;
     usage_record.concession_id = quota_account.concession_id.clone();
     usage_record.shipment_id = shipment_id.clone();
     usage_record.amount = amount;
     usage_record.timestamp = current_time;
     usage_record.holder = quota_account.holder;
     usage_record.quality_params = quality_params.clone();
-    usage_record.source_location = String::new(); // To be updated by mobile app
-    usage_record.destination_location = String::new(); // To be updated by mobile app
-    usage_record.transport_details = String::new(); // To be updated by mobile app
-    usage_record.bump = ctx.bumps.usage_record;
+    usage_record.source_location = String::new(); // Set via update_shipment_details
+    usage_record.destination_location = String::new(); // Set via update_shipment_details
+    usage_record.transport_details = String::new(); // Set via update_shipment_details
+    usage_record.bump = usage_bump;
+    usage_record.flagged_by = None;
+    usage_record.flag_reason = String::new();
+    usage_record.flagged_at = 0;
+    usage_record.quota_account = quota_key;
+    usage_record.sequence = sequence;
+    usage_record.status = ShipmentStatus::Dispatched;
+    usage_record.in_transit_at = 0;
+    usage_record.delivered_at = 0;
+    usage_record.cancelled_at = 0;
+    usage_record.rejected_at = 0;
+    usage_record.payer = payer;
+    usage_record.assay_lab = None; // Set via attach_assay
+    usage_record.assay_sample_id = String::new();
+    usage_record.assayed_params = None;
+    usage_record.assayed_at = 0;
+    usage_record.delegate = delegate;
+    usage_record.grade_debited = remaining_grade_quota.is_some();
 
     // Emit event
     emit!(QuotaUsed {
         concession_id: quota_account.concession_id.clone(),
         shipment_id,
+        sequence,
         amount,
         remaining_quota: quota_account.available_quota,
+        remaining_grade_quota,
         quality_params,
         timestamp: current_time,
     });
@@ -86,30 +233,9 @@ pub fn handler(
     Ok(())
 }
 
-fn validate_quality_parameters(params: &QualityParameters) -> Result<()> {
-    // Validate GCV (2000-8000 kcal/kg is typical range for coal)
-    require!(
-        params.gross_calorific_value >= 2000 && params.gross_calorific_value <= 8000,
-        QuotaError::InvalidGCVValue
-    );
-    
-    // Validate moisture content (0-50%)
-    require!(
-        params.moisture_content <= 5000, // 50.00%
-        QuotaError::InvalidMoistureContent
-    );
-    
-    // Validate ash content (0-50%)
-    require!(
-        params.ash_content <= 5000, // 50.00%
-        QuotaError::InvalidAshContent
-    );
-    
-    // Validate sulphur content (0-10%)
-    require!(
-        params.sulphur_content <= 1000, // 10.00%
-        QuotaError::InvalidSulphurContent
-    );
+fn validate_quality_parameters(params: &QualityParameters, bounds: &QualityBounds) -> Result<()> {
+    // Validate against the quota's quality policy or the default bounds
+    bounds.check(params)?;
     
     // Validate size classification length
     require!(
@@ -118,4 +244,4 @@ fn validate_quality_parameters(params: &QualityParameters) -> Result<()> {
     );
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,212 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+use super::use_quota::{record_usage, Shipment};
+
+/// Maximum shipments per batch, bounded by transaction size and compute.
+/// Larger loads must be split across transactions; whole rakes go through
+/// `dispatch_rake` instead.
+pub const MAX_BATCH_SHIPMENTS: usize = 16;
+
+/// One shipment within a `use_quota_batch` call
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BatchShipment {
+    pub shipment_id: String,
+    pub amount: u64,
+    pub quality_params: QualityParameters,
+}
+
+/// Usage records for the batch are passed as remaining accounts, one per
+/// shipment, at the PDAs for consecutive `usage_count` values starting from
+/// the quota's current count
+#[derive(Accounts)]
+pub struct UseQuotaBatch<'info> {
+    #[account(
+        mut,
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    #[account(
+        seeds = [b"role", quota_account.key().as_ref(), authority.key().as_ref()],
+        bump = role_assignment.bump
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// CHECK: Environmental clearance of the quota, charged in the handler
+    /// once one has been registered at this address
+    #[account(
+        mut,
+        seeds = [b"clearance", quota_account.key().as_ref()],
+        bump
+    )]
+    pub clearance: UncheckedAccount<'info>,
+
+    /// Quality policy assigned to the quota, if any
+    pub quality_policy: Option<Account<'info, QualityPolicy>>,
+
+    /// Holder or dispatch clerk of the quota, pays for the usage records
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UseQuotaBatch<'info>>,
+    shipments: Vec<BatchShipment>,
+) -> Result<()> {
+    authorize(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.authority.key(),
+        ctx.accounts.role_assignment.as_deref(),
+        Action::UseQuota,
+    )?;
+
+    // Validation
+    require!(
+        !shipments.is_empty() && shipments.len() <= MAX_BATCH_SHIPMENTS,
+        QuotaError::InvalidBatchSize
+    );
+    require!(
+        ctx.remaining_accounts.len() == shipments.len(),
+        QuotaError::BatchAccountsMismatch
+    );
+    require!(
+        shipments.iter().enumerate().all(|(index, shipment)| {
+            shipments[..index]
+                .iter()
+                .all(|earlier| earlier.shipment_id != shipment.shipment_id)
+        }),
+        QuotaError::DuplicateShipmentId
+    );
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let quota_key = ctx.accounts.quota_account.key();
+    let first_sequence = ctx.accounts.quota_account.usage_count;
+    let shipment_count = shipments.len() as u64;
+    let mut total_amount: u64 = 0;
+
+    // Each shipment is checked and charged in turn; any failure reverts the batch
+    for (shipment, record_info) in shipments.into_iter().zip(ctx.remaining_accounts) {
+        let sequence = ctx.accounts.quota_account.usage_count;
+        let sequence_bytes = sequence.to_le_bytes();
+        let (expected, bump) = Pubkey::find_program_address(
+            &[b"usage", quota_key.as_ref(), &sequence_bytes],
+            &crate::ID,
+        );
+        require_keys_eq!(record_info.key(), expected, QuotaError::BatchAccountsMismatch);
+
+        create_program_account(
+            record_info,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+            &[b"usage", quota_key.as_ref(), &sequence_bytes, &[bump]],
+            UsageRecord::LEN,
+        )?;
+
+        let mut usage_record = UsageRecord::try_deserialize_unchecked(&mut &record_info.data.borrow()[..])?;
+        let amount = shipment.amount;
+        record_usage(
+            quota_key,
+            &mut ctx.accounts.quota_account,
+            &mut usage_record,
+            bump,
+            &ctx.accounts.clearance,
+            ctx.accounts.quality_policy.as_ref(),
+            Shipment {
+                amount,
+                shipment_id: shipment.shipment_id,
+                quality_params: shipment.quality_params,
+                payer: ctx.accounts.authority.key(),
+                delegate: None,
+            },
+        )?;
+        usage_record.try_serialize(&mut &mut record_info.data.borrow_mut()[..])?;
+        // Only counted once charged, so it never exceeds the allocation
+        total_amount += amount;
+    }
+
+    // Emit event
+    let quota_account = &ctx.accounts.quota_account;
+    emit!(QuotaBatchUsed {
+        concession_id: quota_account.concession_id.clone(),
+        first_sequence,
+        shipment_count,
+        total_amount,
+        remaining_quota: quota_account.available_quota,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+/// Allocate a PDA of `space` bytes owned by this program, as `init` would,
+/// including when someone has already sent lamports to the address
+pub(crate) fn create_program_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    seeds: &[&[u8]],
+    space: usize,
+) -> Result<()> {
+    let required_lamports = Rent::get()?.minimum_balance(space);
+    let signer_seeds = &[seeds];
+
+    if account.lamports() == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+                signer_seeds,
+            ),
+            required_lamports,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let shortfall = required_lamports.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Allocate {
+                account_to_allocate: account.clone(),
+            },
+            signer_seeds,
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Assign {
+                account_to_assign: account.clone(),
+            },
+            signer_seeds,
+        ),
+        &crate::ID,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use crate::access::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct VerifyRakeWagon<'info> {
+    #[account(
+        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
+        bump = quota_account.bump,
+        constraint = QuotaAccount::is_current_layout(quota_account.as_ref()) @ QuotaError::AccountMigrationRequired
+    )]
+    pub quota_account: Account<'info, QuotaAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"rake", rake_shipment.usage_record.as_ref()],
+        bump = rake_shipment.bump,
+        has_one = quota_account @ QuotaError::ShipmentQuotaMismatch
+    )]
+    pub rake_shipment: Account<'info, RakeShipment>,
+
+    /// Usage record the rake was charged on, flagged if a wagon re-weighs
+    /// differently or the wagons do not add up to the declared weight
+    #[account(
+        mut,
+        address = rake_shipment.usage_record @ QuotaError::ShipmentQuotaMismatch,
+        constraint = UsageRecord::is_current_layout(usage_record.as_ref()) @ QuotaError::AccountMigrationRequired,
+        constraint = !matches!(
+            usage_record.status,
+            ShipmentStatus::Cancelled | ShipmentStatus::Rejected
+        ) @ QuotaError::ShipmentWithdrawn
+    )]
+    pub usage_record: Account<'info, UsageRecord>,
+
+    #[account(
+        seeds = [b"role", quota_account.key().as_ref(), inspector.key().as_ref()],
+        bump = role_assignment.bump
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// Inspector or regulator who re-weighed the wagon
+    pub inspector: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<VerifyRakeWagon>,
+    wagon: WagonWeight,
+    proof: Vec<[u8; 32]>,
+    measured_gross_weight_kg: u64,
+) -> Result<()> {
+    authorize(
+        &ctx.accounts.quota_account,
+        ctx.accounts.quota_account.key(),
+        ctx.accounts.inspector.key(),
+        ctx.accounts.role_assignment.as_deref(),
+        Action::VerifyWagon,
+    )?;
+
+    let rake_shipment = &mut ctx.accounts.rake_shipment;
+
+    // Validation
+    require!(wagon.index < rake_shipment.wagon_count, QuotaError::InvalidWagonIndex);
+    require!(
+        !rake_shipment.is_wagon_verified(wagon.index),
+        QuotaError::WagonAlreadyVerified
+    );
+    require!(
+        rake_shipment.proves_wagon(&wagon, &proof)?,
+        QuotaError::InvalidWagonProof
+    );
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let matches_declared = wagon.gross_weight_kg.abs_diff(measured_gross_weight_kg)
+        <= RakeShipment::WAGON_WEIGHT_TOLERANCE_KG;
+
+    rake_shipment.mark_wagon_verified(wagon.index);
+    rake_shipment.verified_net_weight_kg += wagon.net_weight_kg();
+    rake_shipment.measured_net_weight_kg +=
+        measured_gross_weight_kg.saturating_sub(wagon.tare_weight_kg);
+    if !matches_declared {
+        rake_shipment.discrepancies += 1;
+    }
+
+    // A wagon that re-weighs differently, or a rake whose wagons outweigh
+    // the tonnage it was charged for, is flagged for the regulator unless
+    // the shipment is already flagged
+    let usage_record = &mut ctx.accounts.usage_record;
+    if (!matches_declared || rake_shipment.wagons_disagree_with_declaration())
+        && usage_record.flagged_by.is_none()
+    {
+        let reason = if !matches_declared {
+            format!(
+                "Wagon {} of rake {} re-weighed at {} kg gross against {} kg declared",
+                wagon.index,
+                rake_shipment.rake_number,
+                measured_gross_weight_kg,
+                wagon.gross_weight_kg
+            )
+        } else {
+            format!(
+                "Wagons of rake {} weigh {} kg net as declared and {} kg as re-weighed against {} kg declared",
+                rake_shipment.rake_number,
+                rake_shipment.verified_net_weight_kg,
+                rake_shipment.measured_net_weight_kg,
+                rake_shipment.net_weight_kg
+            )
+        };
+        usage_record.flagged_by = Some(ctx.accounts.inspector.key());
+        usage_record.flag_reason = reason.clone();
+        usage_record.flagged_at = current_time;
+
+        emit!(ShipmentFlagged {
+            concession_id: usage_record.concession_id.clone(),
+            shipment_id: usage_record.shipment_id.clone(),
+            flagged_by: ctx.accounts.inspector.key(),
+            role: ctx.accounts.role_assignment.as_ref().map(|assignment| assignment.role),
+            reason,
+            timestamp: current_time,
+        });
+    }
+
+    // Emit event
+    emit!(WagonVerified {
+        concession_id: ctx.accounts.quota_account.concession_id.clone(),
+        rake_number: rake_shipment.rake_number.clone(),
+        index: wagon.index,
+        wagon_number: wagon.wagon_number,
+        declared_gross_weight_kg: wagon.gross_weight_kg,
+        measured_gross_weight_kg,
+        matches_declared,
+        verified_count: rake_shipment.verified_count,
+        verified_by: ctx.accounts.inspector.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -2,6 +2,14 @@
 
 use anchor_lang::prelude::*;
 
+pub mod access;
+pub mod errors;
+pub mod instructions;
+pub mod state;
+
+pub use instructions::*;
+pub use state::*;
+
 // Ensure this matches your program's ID from Anchor.toml
 declare_id!("9v8P6i1esz4orrqTx9X5EhV28hifkT4Q3HEoGLPeKmPn");
 
@@ -15,41 +23,18 @@ pub mod quotamanagement {
         allocated_quota: u64,
         validity_period: i64,
         quota_type: QuotaType,
+        mining_region: String,
+        environmental_clearance: String,
     ) -> Result<()> {
-        require!(allocated_quota > 0, QuotaError::InvalidQuotaAmount);
-        require!(
-            validity_period > Clock::get()?.unix_timestamp,
-            QuotaError::InvalidValidityPeriod
-        );
-        require!(
-            concession_id.len() <= QuotaAccount::MAX_CONCESSION_ID_LEN,
-            QuotaError::ConcessionIdTooLong
-        );
-
-        let quota_account = &mut ctx.accounts.quota_account;
-        let current_time = Clock::get()?.unix_timestamp;
-
-        quota_account.concession_id = concession_id.clone();
-        quota_account.holder = ctx.accounts.holder.key();
-        quota_account.regulator = ctx.accounts.regulator.key();
-        quota_account.allocated_quota = allocated_quota;
-        quota_account.used_quota = 0;
-        quota_account.available_quota = allocated_quota;
-        quota_account.validity_period = validity_period;
-        quota_account.quota_type = quota_type;
-        quota_account.status = QuotaStatus::Active;
-        quota_account.created_at = current_time;
-        quota_account.updated_at = current_time;
-        quota_account.bump = ctx.bumps.quota_account;
-
-        emit!(QuotaInitialized {
+        instructions::initialize_quota::handler(
+            ctx,
             concession_id,
-            holder: quota_account.holder,
             allocated_quota,
             validity_period,
-        });
-
-        Ok(())
+            quota_type,
+            mining_region,
+            environmental_clearance,
+        )
     }
 
     pub fn use_quota(
@@ -58,296 +43,369 @@ pub mod quotamanagement {
         shipment_id: String,
         quality_params: QualityParameters,
     ) -> Result<()> {
-        let quota_account = &mut ctx.accounts.quota_account;
-        let current_time = Clock::get()?.unix_timestamp;
-
-        // Validation
-        require!(quota_account.status == QuotaStatus::Active, QuotaError::QuotaNotActive);
-        require!(current_time <= quota_account.validity_period, QuotaError::QuotaExpired);
-        require!(amount > 0, QuotaError::InvalidUsageAmount);
-        require!(quota_account.available_quota >= amount, QuotaError::InsufficientQuota);
-        require!(
-            shipment_id.len() <= UsageRecord::MAX_SHIPMENT_ID_LEN,
-            QuotaError::ShipmentIdTooLong
-        );
-        validate_quality_parameters(&quality_params)?;
-
-        // Update quota account
-        quota_account.used_quota += amount;
-        quota_account.available_quota -= amount;
-        quota_account.updated_at = current_time;
-
-        // Check if quota is now exhausted
-        if quota_account.available_quota == 0 {
-            quota_account.status = QuotaStatus::Exhausted;
-        }
-
-        // Record usage
-        let usage_record = &mut ctx.accounts.usage_record;
-        usage_record.concession_id = quota_account.concession_id.clone();
-        usage_record.shipment_id = shipment_id.clone();
-        usage_record.amount = amount;
-        usage_record.timestamp = current_time;
-        usage_record.holder = quota_account.holder;
-        usage_record.quality_params = quality_params.clone();
-        usage_record.source_location = String::new(); // To be updated by mobile app
-        usage_record.destination_location = String::new(); // To be updated by mobile app
-        usage_record.transport_details = String::new(); // To be updated by mobile app
-        usage_record.bump = ctx.bumps.usage_record;
-
-        // Emit event
-        emit!(QuotaUsed {
-            concession_id: quota_account.concession_id.clone(),
-            shipment_id,
-            amount,
-            remaining_quota: quota_account.available_quota,
-            quality_params,
-            timestamp: current_time,
-        });
+        instructions::use_quota::handler(ctx, amount, shipment_id, quality_params)
+    }
 
-        Ok(())
+    pub fn request_transfer(
+        ctx: Context<RequestTransfer>,
+        amount: u64,
+        transfer_reason: String,
+        transfer_type: TransferType,
+    ) -> Result<()> {
+        instructions::request_transfer::handler(ctx, amount, transfer_reason, transfer_type)
     }
-}
 
-// Accounts
-#[derive(Accounts)]
-#[instruction(concession_id: String)]
-pub struct InitializeQuota<'info> {
-    #[account(
-        init,
-        payer = regulator,
-        space = 8 + QuotaAccount::INIT_SPACE,
-        seeds = [b"quota", concession_id.as_bytes(), holder.key().as_ref()],
-        bump
-    )]
-    pub quota_account: Account<'info, QuotaAccount>,
-    
-    /// CHECK: Holder of the quota
-    pub holder: AccountInfo<'info>,
-    
-    #[account(mut)]
-    pub regulator: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    pub fn approve_transfer(ctx: Context<ApproveTransfer>) -> Result<()> {
+        instructions::approve_transfer::handler(ctx)
+    }
 
-#[derive(Accounts)]
-#[instruction(amount: u64, shipment_id: String)]
-pub struct UseQuota<'info> {
-    #[account(
-        mut,
-        seeds = [b"quota", quota_account.concession_id.as_bytes(), quota_account.holder.as_ref()],
-        bump = quota_account.bump,
-        has_one = holder
-    )]
-    pub quota_account: Account<'info, QuotaAccount>,
-
-    #[account(
-        init,
-        payer = holder,
-        space = 8 + UsageRecord::INIT_SPACE,
-        seeds = [b"usage", shipment_id.as_bytes(), holder.key().as_ref()],
-        bump
-    )]
-    pub usage_record: Account<'info, UsageRecord>,
-    
-    #[account(mut)]
-    pub holder: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    pub fn reject_transfer(ctx: Context<RejectTransfer>, rejection_reason: String) -> Result<()> {
+        instructions::reject_transfer::handler(ctx, rejection_reason)
+    }
 
-// State
-#[account]
-pub struct QuotaAccount {
-    pub concession_id: String,
-    pub holder: Pubkey,
-    pub regulator: Pubkey,
-    pub allocated_quota: u64,
-    pub used_quota: u64,
-    pub available_quota: u64,
-    pub validity_period: i64,
-    pub quota_type: QuotaType,
-    pub status: QuotaStatus,
-    pub created_at: i64,
-    pub updated_at: i64,
-    pub bump: u8,
-}
+    pub fn accept_transfer(ctx: Context<AcceptTransfer>) -> Result<()> {
+        instructions::accept_transfer::handler(ctx)
+    }
 
-impl QuotaAccount {
-    pub const MAX_CONCESSION_ID_LEN: usize = 32;
-    pub const INIT_SPACE: usize = 
-        4 + Self::MAX_CONCESSION_ID_LEN + // concession_id (String)
-        32 + // holder (Pubkey)
-        32 + // regulator (Pubkey)
-        8 + // allocated_quota (u64)
-        8 + // used_quota (u64)
-        8 + // available_quota (u64)
-        8 + // validity_period (i64)
-        1 + 1 + // quota_type (Enum)
-        1 + 1 + // status (Enum)
-        8 + // created_at (i64)
-        8 + // updated_at (i64)
-        1; // bump (u8)
-}
+    pub fn execute_transfer(ctx: Context<ExecuteTransfer>) -> Result<()> {
+        instructions::execute_transfer::handler(ctx)
+    }
 
-#[account]
-pub struct UsageRecord {
-    pub concession_id: String,
-    pub shipment_id: String,
-    pub amount: u64,
-    pub timestamp: i64,
-    pub holder: Pubkey,
-    pub quality_params: QualityParameters,
-    pub source_location: String,
-    pub destination_location: String,
-    pub transport_details: String,
-    pub bump: u8,
-}
+    pub fn cancel_transfer(ctx: Context<CancelTransfer>) -> Result<()> {
+        instructions::cancel_transfer::handler(ctx)
+    }
 
-impl UsageRecord {
-    pub const MAX_SHIPMENT_ID_LEN: usize = 32;
-    pub const INIT_SPACE: usize =
-        4 + Self::MAX_SHIPMENT_ID_LEN + // shipment_id (String)
-        4 + QuotaAccount::MAX_CONCESSION_ID_LEN + // concession_id (String)
-        8 + // amount (u64)
-        8 + // timestamp (i64)
-        32 + // holder (Pubkey)
-        QualityParameters::INIT_SPACE + // quality_params
-        4 + 32 + // source_location (String)
-        4 + 32 + // destination_location (String)
-        4 + 64 + // transport_details (String)
-        1; // bump (u8)
-}
+    pub fn update_quota(
+        ctx: Context<UpdateQuota>,
+        new_allocated_quota: Option<u64>,
+        new_validity_period: Option<i64>,
+        status: Option<QuotaStatus>,
+        update_reason: String,
+    ) -> Result<()> {
+        instructions::update_quota::handler(
+            ctx,
+            new_allocated_quota,
+            new_validity_period,
+            status,
+            update_reason,
+        )
+    }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub struct QualityParameters {
-    pub gross_calorific_value: u16,
-    pub moisture_content: u16,
-    pub ash_content: u16,
-    pub sulphur_content: u16,
-    pub volatile_matter: u16,
-    pub fixed_carbon: u16,
-    pub coal_grade: CoalGrade,
-    pub size_classification: String,
-}
+    pub fn suspend_quota(ctx: Context<SuspendQuota>, reason: String) -> Result<()> {
+        instructions::suspend_quota::handler(ctx, reason)
+    }
 
-impl QualityParameters {
-    pub const MAX_SIZE_CLASSIFICATION_LEN: usize = 16;
-    pub const INIT_SPACE: usize = 
-        2 + // gross_calorific_value
-        2 + // moisture_content
-        2 + // ash_content
-        2 + // sulphur_content
-        2 + // volatile_matter
-        2 + // fixed_carbon
-        1 + 1 + // coal_grade
-        4 + Self::MAX_SIZE_CLASSIFICATION_LEN; // size_classification (String)
-}
+    pub fn reactivate_quota(ctx: Context<ReactivateQuota>) -> Result<()> {
+        instructions::reactivate_quota::handler(ctx)
+    }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum CoalGrade {
-    GradeA,
-    GradeB,
-    GradeC,
-    GradeD,
-}
+    pub fn migrate_quota_account(ctx: Context<MigrateQuotaAccount>) -> Result<()> {
+        instructions::migrate_quota_account::handler(ctx)
+    }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum QuotaStatus {
-    Active,
-    Suspended,
-    Expired,
-    Exhausted,
-}
+    pub fn migrate_usage_record(ctx: Context<MigrateUsageRecord>) -> Result<()> {
+        instructions::migrate_usage_record::handler(ctx)
+    }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum QuotaType {
-    Annual,
-    Monthly,
-}
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        instructions::initialize_config::handler(ctx)
+    }
 
-// Events
-#[event]
-pub struct QuotaInitialized {
-    pub concession_id: String,
-    pub holder: Pubkey,
-    pub allocated_quota: u64,
-    pub validity_period: i64,
-}
+    pub fn add_regulator(
+        ctx: Context<AddRegulator>,
+        name: String,
+        jurisdiction: String,
+    ) -> Result<()> {
+        instructions::add_regulator::handler(ctx, name, jurisdiction)
+    }
 
-#[event]
-pub struct QuotaUsed {
-    pub concession_id: String,
-    pub shipment_id: String,
-    pub amount: u64,
-    pub remaining_quota: u64,
-    pub quality_params: QualityParameters,
-    pub timestamp: i64,
-}
+    pub fn disable_regulator(ctx: Context<DisableRegulator>) -> Result<()> {
+        instructions::disable_regulator::handler(ctx)
+    }
 
-// Errors
-#[error_code]
-pub enum QuotaError {
-    #[msg("Invalid quota amount")]
-    InvalidQuotaAmount,
-    #[msg("Invalid validity period")]
-    InvalidValidityPeriod,
-    #[msg("Concession ID too long")]
-    ConcessionIdTooLong,
-    #[msg("Quota is not active")]
-    QuotaNotActive,
-    #[msg("Quota has expired")]
-    QuotaExpired,
-    #[msg("Invalid usage amount")]
-    InvalidUsageAmount,
-    #[msg("Insufficient quota available")]
-    InsufficientQuota,
-    #[msg("Shipment ID too long")]
-    ShipmentIdTooLong,
-    #[msg("Invalid GCV Value")]
-    InvalidGCVValue,
-    #[msg("Invalid moisture content")]
-    InvalidMoistureContent,
-    #[msg("Invalid ash content")]
-    InvalidAshContent,
-    #[msg("Invalid sulphur content")]
-    InvalidSulphurContent,
-    #[msg("Size classification too long")]
-    SizeClassificationTooLong,
-}
+    pub fn enable_regulator(ctx: Context<EnableRegulator>) -> Result<()> {
+        instructions::enable_regulator::handler(ctx)
+    }
+
+    pub fn remove_regulator(ctx: Context<RemoveRegulator>) -> Result<()> {
+        instructions::remove_regulator::handler(ctx)
+    }
+
+    pub fn grant_role(ctx: Context<GrantRole>, role: Role) -> Result<()> {
+        instructions::grant_role::handler(ctx, role)
+    }
+
+    pub fn revoke_role(ctx: Context<RevokeRole>) -> Result<()> {
+        instructions::revoke_role::handler(ctx)
+    }
+
+    pub fn flag_shipment(ctx: Context<FlagShipment>, reason: String) -> Result<()> {
+        instructions::flag_shipment::handler(ctx, reason)
+    }
+
+    pub fn create_delegate(
+        ctx: Context<CreateDelegate>,
+        max_tonnage: u64,
+        daily_cap: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::create_delegate::handler(ctx, max_tonnage, daily_cap, expires_at)
+    }
+
+    pub fn revoke_delegate(ctx: Context<RevokeDelegate>) -> Result<()> {
+        instructions::revoke_delegate::handler(ctx)
+    }
+
+    pub fn update_shipment_details(
+        ctx: Context<UpdateShipmentDetails>,
+        source_location: Option<String>,
+        destination_location: Option<String>,
+        transport_details: Option<String>,
+    ) -> Result<()> {
+        instructions::update_shipment_details::handler(
+            ctx,
+            source_location,
+            destination_location,
+            transport_details,
+        )
+    }
+
+    pub fn mark_shipment_in_transit(ctx: Context<MarkShipmentInTransit>) -> Result<()> {
+        instructions::mark_shipment_in_transit::handler(ctx)
+    }
+
+    pub fn confirm_delivery(ctx: Context<ConfirmDelivery>) -> Result<()> {
+        instructions::confirm_delivery::handler(ctx)
+    }
+
+    pub fn reject_shipment(ctx: Context<RejectShipment>, reason: String) -> Result<()> {
+        instructions::reject_shipment::handler(ctx, reason)
+    }
+
+    pub fn cancel_shipment(ctx: Context<CancelShipment>) -> Result<()> {
+        instructions::cancel_shipment::handler(ctx)
+    }
+
+    pub fn set_cancellation_window(
+        ctx: Context<SetCancellationWindow>,
+        window_seconds: i64,
+    ) -> Result<()> {
+        instructions::set_cancellation_window::handler(ctx, window_seconds)
+    }
+
+    pub fn set_crank_reward(ctx: Context<SetCrankReward>, reward_lamports: u64) -> Result<()> {
+        instructions::set_crank_reward::handler(ctx, reward_lamports)
+    }
+
+    pub fn expire_quota(ctx: Context<ExpireQuota>) -> Result<()> {
+        instructions::expire_quota::handler(ctx)
+    }
+
+    pub fn revoke_quota(
+        ctx: Context<RevokeQuota>,
+        reason_code: RevocationReason,
+        details: String,
+    ) -> Result<()> {
+        instructions::revoke_quota::handler(ctx, reason_code, details)
+    }
+
+    pub fn close_quota_account(ctx: Context<CloseQuotaAccount>) -> Result<()> {
+        instructions::close_quota_account::handler(ctx)
+    }
+
+    pub fn close_usage_record(ctx: Context<CloseUsageRecord>) -> Result<()> {
+        instructions::close_usage_record::handler(ctx)
+    }
+
+    pub fn close_transfer_record(ctx: Context<CloseTransferRecord>) -> Result<()> {
+        instructions::close_transfer_record::handler(ctx)
+    }
+
+    pub fn update_quota_metadata(
+        ctx: Context<UpdateQuotaMetadata>,
+        mining_region: Option<String>,
+        environmental_clearance: Option<String>,
+    ) -> Result<()> {
+        instructions::update_quota_metadata::handler(ctx, mining_region, environmental_clearance)
+    }
+
+    pub fn register_clearance(
+        ctx: Context<RegisterClearance>,
+        issuing_authority: String,
+        reference_number: String,
+        document_hash: [u8; 32],
+        annual_capacity: u64,
+        valid_from: i64,
+        valid_until: i64,
+    ) -> Result<()> {
+        instructions::register_clearance::handler(
+            ctx,
+            issuing_authority,
+            reference_number,
+            document_hash,
+            annual_capacity,
+            valid_from,
+            valid_until,
+        )
+    }
+
+    pub fn update_clearance(
+        ctx: Context<UpdateClearance>,
+        reference_number: Option<String>,
+        document_hash: Option<[u8; 32]>,
+        annual_capacity: Option<u64>,
+        valid_until: Option<i64>,
+    ) -> Result<()> {
+        instructions::update_clearance::handler(
+            ctx,
+            reference_number,
+            document_hash,
+            annual_capacity,
+            valid_until,
+        )
+    }
+
+    pub fn set_clearance_status(
+        ctx: Context<SetClearanceStatus>,
+        status: ClearanceStatus,
+        reason: String,
+    ) -> Result<()> {
+        instructions::set_clearance_status::handler(ctx, status, reason)
+    }
+
+    pub fn create_quality_policy(
+        ctx: Context<CreateQualityPolicy>,
+        policy_id: String,
+        region: String,
+        bounds: QualityBounds,
+    ) -> Result<()> {
+        instructions::create_quality_policy::handler(ctx, policy_id, region, bounds)
+    }
+
+    pub fn update_quality_policy(
+        ctx: Context<UpdateQualityPolicy>,
+        bounds: QualityBounds,
+    ) -> Result<()> {
+        instructions::update_quality_policy::handler(ctx, bounds)
+    }
+
+    pub fn assign_quality_policy(ctx: Context<AssignQualityPolicy>) -> Result<()> {
+        instructions::assign_quality_policy::handler(ctx)
+    }
+
+    pub fn set_grade_allocation(
+        ctx: Context<SetGradeAllocation>,
+        grade: CoalGrade,
+        allocated: u64,
+    ) -> Result<()> {
+        instructions::set_grade_allocation::handler(ctx, grade, allocated)
+    }
+
+    pub fn register_lab(
+        ctx: Context<RegisterLab>,
+        name: String,
+        accreditation_id: String,
+    ) -> Result<()> {
+        instructions::register_lab::handler(ctx, name, accreditation_id)
+    }
+
+    pub fn set_lab_status(ctx: Context<SetLabStatus>, status: LabStatus) -> Result<()> {
+        instructions::set_lab_status::handler(ctx, status)
+    }
+
+    pub fn attach_assay(
+        ctx: Context<AttachAssay>,
+        sample_id: String,
+        quality_params: QualityParameters,
+    ) -> Result<()> {
+        instructions::attach_assay::handler(ctx, sample_id, quality_params)
+    }
 
-// Helper function
-fn validate_quality_parameters(params: &QualityParameters) -> Result<()> {
-    // Validate GCV (2000-8000 kcal/kg is typical range for coal)
-    require!(
-        params.gross_calorific_value >= 2000 && params.gross_calorific_value <= 8000,
-        QuotaError::InvalidGCVValue
-    );
-    
-    // Validate moisture content (0-50%)
-    require!(
-        params.moisture_content <= 5000, // 50.00%
-        QuotaError::InvalidMoistureContent
-    );
-    
-    // Validate ash content (0-50%)
-    require!(
-        params.ash_content <= 5000, // 50.00%
-        QuotaError::InvalidAshContent
-    );
-    
-    // Validate sulphur content (0-10%)
-    require!(
-        params.sulphur_content <= 1000, // 10.00%
-        QuotaError::InvalidSulphurContent
-    );
-    
-    // Validate size classification length
-    require!(
-        params.size_classification.len() <= QualityParameters::MAX_SIZE_CLASSIFICATION_LEN,
-        QuotaError::SizeClassificationTooLong
-    );
-
-    Ok(())
-}
\ No newline at end of file
+    pub fn set_utilization_thresholds(
+        ctx: Context<SetUtilizationThresholds>,
+        warning_thresholds: Vec<u8>,
+        utilization_cap: Option<u8>,
+    ) -> Result<()> {
+        instructions::set_utilization_thresholds::handler(ctx, warning_thresholds, utilization_cap)
+    }
+
+    pub fn set_dispatch_limits(
+        ctx: Context<SetDispatchLimits>,
+        dispatch_limits: DispatchLimits,
+    ) -> Result<()> {
+        instructions::set_dispatch_limits::handler(ctx, dispatch_limits)
+    }
+
+    pub fn reserve_quota(
+        ctx: Context<ReserveQuota>,
+        amount: u64,
+        reference: String,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::reserve_quota::handler(ctx, amount, reference, expires_at)
+    }
+
+    pub fn release_reservation(ctx: Context<ReleaseReservation>) -> Result<()> {
+        instructions::release_reservation::handler(ctx)
+    }
+
+    pub fn consume_reservation(
+        ctx: Context<ConsumeReservation>,
+        shipment_id: String,
+        quality_params: QualityParameters,
+    ) -> Result<()> {
+        instructions::consume_reservation::handler(ctx, shipment_id, quality_params)
+    }
+
+    pub fn use_quota_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UseQuotaBatch<'info>>,
+        shipments: Vec<BatchShipment>,
+    ) -> Result<()> {
+        instructions::use_quota_batch::handler(ctx, shipments)
+    }
+
+    pub fn dispatch_rake(
+        ctx: Context<DispatchRake>,
+        rake_number: String,
+        siding_code: String,
+        wagon_count: u16,
+        net_weight_kg: u64,
+        wagon_root: [u8; 32],
+        quality_params: QualityParameters,
+    ) -> Result<()> {
+        instructions::dispatch_rake::handler(
+            ctx,
+            rake_number,
+            siding_code,
+            wagon_count,
+            net_weight_kg,
+            wagon_root,
+            quality_params,
+        )
+    }
+
+    pub fn verify_rake_wagon(
+        ctx: Context<VerifyRakeWagon>,
+        wagon: WagonWeight,
+        proof: Vec<[u8; 32]>,
+        measured_gross_weight_kg: u64,
+    ) -> Result<()> {
+        instructions::verify_rake_wagon::handler(ctx, wagon, proof, measured_gross_weight_kg)
+    }
+
+    pub fn close_rake_shipment(ctx: Context<CloseRakeShipment>) -> Result<()> {
+        instructions::close_rake_shipment::handler(ctx)
+    }
+
+    pub fn close_clearance(ctx: Context<CloseClearance>) -> Result<()> {
+        instructions::close_clearance::handler(ctx)
+    }
+
+    pub fn close_role_assignment(ctx: Context<CloseRoleAssignment>) -> Result<()> {
+        instructions::close_role_assignment::handler(ctx)
+    }
+
+    pub fn close_delegate(ctx: Context<CloseDelegate>) -> Result<()> {
+        instructions::close_delegate::handler(ctx)
+    }
+}
@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::QuotaError;
+
+/// Environmental clearance backing a single quota, limiting how much may be
+/// dispatched per clearance year
+#[account]
+pub struct EnvironmentalClearance {
+    /// Quota account the clearance applies to
+    pub quota_account: Pubkey,
+    /// Authority that granted the clearance (e.g. MoEFCC, SEIAA)
+    pub issuing_authority: String,
+    /// Reference number printed on the clearance letter
+    pub reference_number: String,
+    /// SHA-256 hash of the clearance document
+    pub document_hash: [u8; 32],
+    /// Maximum tonnage that may be dispatched per clearance year
+    pub annual_capacity: u64,
+    /// Unix timestamp from which the clearance applies
+    pub valid_from: i64,
+    /// Unix timestamp after which the clearance lapses
+    pub valid_until: i64,
+    /// Current status of the clearance
+    pub status: ClearanceStatus,
+    /// Clearance year (whole years since `valid_from`) that `used_this_year`
+    /// refers to
+    pub current_year: i64,
+    /// Tonnage dispatched during `current_year`
+    pub used_this_year: u64,
+    /// Regulator who registered the clearance
+    pub registered_by: Pubkey,
+    /// Timestamp when the clearance was registered
+    pub created_at: i64,
+    /// Timestamp when the clearance was last updated
+    pub updated_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl EnvironmentalClearance {
+    pub const MAX_ISSUING_AUTHORITY_LEN: usize = 64;
+    pub const MAX_REFERENCE_NUMBER_LEN: usize = 64;
+    pub const SECONDS_PER_YEAR: i64 = 365 * 86_400;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // quota_account
+        4 + Self::MAX_ISSUING_AUTHORITY_LEN + // issuing_authority
+        4 + Self::MAX_REFERENCE_NUMBER_LEN + // reference_number
+        32 + // document_hash
+        8 + // annual_capacity
+        8 + // valid_from
+        8 + // valid_until
+        1 + 1 + // status (enum + padding)
+        8 + // current_year
+        8 + // used_this_year
+        32 + // registered_by
+        8 + // created_at
+        8 + // updated_at
+        1; // bump
+
+    /// Clearance year containing `now`
+    pub fn year_of(&self, now: i64) -> i64 {
+        (now - self.valid_from).div_euclid(Self::SECONDS_PER_YEAR)
+    }
+
+    /// Charge `amount` against the capacity of the current clearance year
+    pub fn consume(&mut self, amount: u64, now: i64) -> Result<()> {
+        require!(
+            self.status == ClearanceStatus::Active,
+            QuotaError::ClearanceSuspended
+        );
+        require!(
+            now >= self.valid_from && now <= self.valid_until,
+            QuotaError::ClearanceExpired
+        );
+
+        let year = self.year_of(now);
+        if year != self.current_year {
+            self.current_year = year;
+            self.used_this_year = 0;
+        }
+        require!(
+            self.used_this_year + amount <= self.annual_capacity,
+            QuotaError::ClearanceCapacityExceeded
+        );
+
+        self.used_this_year += amount;
+
+        Ok(())
+    }
+
+    /// Give back `amount` consumed by a shipment dispatched at
+    /// `dispatched_at` that never left the mine. Capacity of an earlier
+    /// clearance year has already lapsed and is not restored, and shipments
+    /// dispatched before the clearance was registered were never charged.
+    pub fn refund(&mut self, amount: u64, dispatched_at: i64) {
+        if dispatched_at >= self.created_at && self.year_of(dispatched_at) == self.current_year {
+            self.used_this_year = self.used_this_year.saturating_sub(amount);
+        }
+    }
+}
+
+// Enums
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum ClearanceStatus {
+    /// Dispatches may proceed within the clearance's validity and capacity
+    Active,
+    /// Clearance held in abeyance by the issuing authority
+    Suspended,
+}
+
+// Events
+
+#[event]
+pub struct ClearanceRegistered {
+    pub concession_id: String,
+    pub issuing_authority: String,
+    pub reference_number: String,
+    pub document_hash: [u8; 32],
+    pub annual_capacity: u64,
+    pub valid_from: i64,
+    pub valid_until: i64,
+    pub registered_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClearanceUpdated {
+    pub concession_id: String,
+    pub reference_number: String,
+    pub document_hash: [u8; 32],
+    pub annual_capacity: u64,
+    pub valid_until: i64,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClearanceStatusUpdated {
+    pub concession_id: String,
+    pub old_status: ClearanceStatus,
+    pub new_status: ClearanceStatus,
+    pub reason: String,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClearanceArchived {
+    pub quota_account: Pubkey,
+    pub reference_number: String,
+    pub annual_capacity: u64,
+    pub used_this_year: u64,
+    pub closed_by: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::QuotaError;
+
+/// Dispatch key authorised by the holder to use a single quota
+#[account]
+pub struct Delegate {
+    /// Quota account the delegate may draw from
+    pub quota_account: Pubkey,
+    /// Public key of the dispatch authority
+    pub delegate: Pubkey,
+    /// Total tonnage the delegate may dispatch
+    pub max_tonnage: u64,
+    /// Tonnage dispatched by the delegate so far
+    pub used_tonnage: u64,
+    /// Maximum tonnage per UTC day
+    pub daily_cap: u64,
+    /// UTC day (unix days) that `used_today` refers to
+    pub current_day: i64,
+    /// Tonnage dispatched during `current_day`
+    pub used_today: u64,
+    /// Unix timestamp after which the delegate is no longer valid
+    pub expires_at: i64,
+    /// Timestamp when the delegate was created
+    pub created_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+    /// Account that paid for the delegate and reclaims its rent
+    pub payer: Pubkey,
+}
+
+impl Delegate {
+    pub const SECONDS_PER_DAY: i64 = 86_400;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // quota_account
+        32 + // delegate
+        8 + // max_tonnage
+        8 + // used_tonnage
+        8 + // daily_cap
+        8 + // current_day
+        8 + // used_today
+        8 + // expires_at
+        8 + // created_at
+        1 + // bump
+        32; // payer
+
+    /// Check if the delegate authorisation has not yet expired
+    pub fn is_active(&self, now: i64) -> bool {
+        now <= self.expires_at
+    }
+
+    /// Tonnage the delegate may still dispatch in total
+    pub fn remaining_allowance(&self) -> u64 {
+        self.max_tonnage.saturating_sub(self.used_tonnage)
+    }
+
+    /// Charge `amount` against the delegate's allowance and daily cap
+    pub fn consume(&mut self, amount: u64, now: i64) -> Result<()> {
+        require!(self.is_active(now), QuotaError::DelegateExpired);
+        require!(
+            amount <= self.remaining_allowance(),
+            QuotaError::DelegateAllowanceExceeded
+        );
+
+        let today = now.div_euclid(Self::SECONDS_PER_DAY);
+        if today != self.current_day {
+            self.current_day = today;
+            self.used_today = 0;
+        }
+        require!(
+            self.used_today + amount <= self.daily_cap,
+            QuotaError::DelegateDailyCapExceeded
+        );
+
+        self.used_tonnage += amount;
+        self.used_today += amount;
+
+        Ok(())
+    }
+
+    /// Give back `amount` charged by a shipment dispatched at `dispatched_at`
+    /// that was later cancelled
+    pub fn refund(&mut self, amount: u64, dispatched_at: i64) {
+        self.used_tonnage = self.used_tonnage.saturating_sub(amount);
+        if dispatched_at.div_euclid(Self::SECONDS_PER_DAY) == self.current_day {
+            self.used_today = self.used_today.saturating_sub(amount);
+        }
+    }
+}
+
+// Events
+
+#[event]
+pub struct DelegateCreated {
+    pub concession_id: String,
+    pub delegate: Pubkey,
+    pub max_tonnage: u64,
+    pub daily_cap: u64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegateRevoked {
+    pub concession_id: String,
+    pub delegate: Pubkey,
+    pub used_tonnage: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegateArchived {
+    pub quota_account: Pubkey,
+    pub delegate: Pubkey,
+    pub used_tonnage: u64,
+    pub closed_by: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+
+use super::QualityParameters;
+
+/// Registry entry for an accredited testing laboratory whose signed assays
+/// may be attached to shipments
+#[account]
+pub struct LabRecord {
+    /// Key the lab signs assay payloads with
+    pub lab: Pubkey,
+    /// Name of the laboratory
+    pub name: String,
+    /// Accreditation certificate number (e.g. NABL)
+    pub accreditation_id: String,
+    /// Whether the lab's assays are currently accepted
+    pub status: LabStatus,
+    /// Admin who registered the lab
+    pub added_by: Pubkey,
+    /// Timestamp when the lab was registered
+    pub added_at: i64,
+    /// Timestamp when the record was last updated
+    pub updated_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl LabRecord {
+    pub const MAX_NAME_LEN: usize = 64;
+    pub const MAX_ACCREDITATION_ID_LEN: usize = 32;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // lab
+        4 + Self::MAX_NAME_LEN + // name
+        4 + Self::MAX_ACCREDITATION_ID_LEN + // accreditation_id
+        1 + 1 + // status (enum + padding)
+        32 + // added_by
+        8 + // added_at
+        8 + // updated_at
+        1; // bump
+
+    /// Check if the lab's assays are currently accepted
+    pub fn is_active(&self) -> bool {
+        self.status == LabStatus::Active
+    }
+}
+
+/// Message a lab signs with its key to attest a shipment's quality. The
+/// Borsh encoding is what the Ed25519 signature covers.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AssayPayload {
+    /// Usage record the assay applies to, so it cannot be replayed on
+    /// another shipment
+    pub usage_record: Pubkey,
+    /// Lab's identifier for the sample
+    pub sample_id: String,
+    /// Quality parameters measured by the lab
+    pub quality_params: QualityParameters,
+}
+
+// Enums
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum LabStatus {
+    /// Assays signed by the lab are accepted
+    Active,
+    /// Accreditation lapsed or under review
+    Suspended,
+}
+
+// Events
+
+#[event]
+pub struct LabRegistered {
+    pub lab: Pubkey,
+    pub name: String,
+    pub accreditation_id: String,
+    pub added_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AssayAttached {
+    pub concession_id: String,
+    pub shipment_id: String,
+    pub sequence: u64,
+    pub lab: Pubkey,
+    pub sample_id: String,
+    pub assayed_params: QualityParameters,
+    pub matches_declared: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LabStatusUpdated {
+    pub lab: Pubkey,
+    pub old_status: LabStatus,
+    pub new_status: LabStatus,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,242 @@
+use anchor_lang::prelude::*;
+
+use super::{
+    CoalGrade, DispatchLimits, DispatchWindow, QualityParameters, QuotaAccount, QuotaStatus,
+    QuotaType, ShipmentStatus, UsageRecord,
+};
+
+// Layouts written by the first release of the program, which kept its own
+// copies of the account types in lib.rs. They share the discriminators of the
+// current accounts but are smaller, so the only way to tell them apart is the
+// allocated data length. Nothing here is ever written back to chain.
+
+/// Quota account as laid out by the first release
+#[derive(AnchorDeserialize)]
+pub struct LegacyQuotaAccount {
+    pub concession_id: String,
+    pub holder: Pubkey,
+    pub regulator: Pubkey,
+    pub allocated_quota: u64,
+    pub used_quota: u64,
+    pub available_quota: u64,
+    pub validity_period: i64,
+    pub quota_type: LegacyQuotaType,
+    pub status: LegacyQuotaStatus,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl LegacyQuotaAccount {
+    pub const MAX_CONCESSION_ID_LEN: usize = 32;
+
+    pub const LEN: usize = 8 + // discriminator
+        4 + Self::MAX_CONCESSION_ID_LEN + // concession_id
+        32 + // holder
+        32 + // regulator
+        8 + // allocated_quota
+        8 + // used_quota
+        8 + // available_quota
+        8 + // validity_period
+        1 + 1 + // quota_type (enum + padding)
+        1 + 1 + // status (enum + padding)
+        8 + // created_at
+        8 + // updated_at
+        1; // bump
+
+    /// Convert into the canonical layout, leaving the fields the first
+    /// release did not have empty
+    pub fn into_current(self) -> QuotaAccount {
+        QuotaAccount {
+            layout_version: QuotaAccount::LAYOUT_VERSION,
+            concession_id: self.concession_id,
+            holder: self.holder,
+            regulator: self.regulator,
+            allocated_quota: self.allocated_quota,
+            used_quota: self.used_quota,
+            available_quota: self.available_quota,
+            validity_period: self.validity_period,
+            status: self.status.into(),
+            quota_type: self.quota_type.into(),
+            mining_region: String::new(),
+            environmental_clearance: String::new(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            bump: self.bump,
+            transfer_count: 0,
+            usage_count: 0,
+            revoked_by: None,
+            revoked_at: 0,
+            revocation_reason: None,
+            open_shipments: 0,
+            quality_policy: None,
+            grade_allocations: Vec::new(),
+            warning_thresholds: QuotaAccount::DEFAULT_WARNING_THRESHOLDS.to_vec(),
+            highest_threshold_crossed: 0,
+            utilization_cap: None,
+            dispatch_limits: DispatchLimits::default(),
+            dispatch_window: DispatchWindow::default(),
+            reserved_quota: 0,
+            reservation_count: 0,
+            open_transfers: 0,
+        }
+    }
+}
+
+/// Usage record as laid out by the first release
+#[derive(AnchorDeserialize)]
+pub struct LegacyUsageRecord {
+    pub concession_id: String,
+    pub shipment_id: String,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub holder: Pubkey,
+    pub quality_params: LegacyQualityParameters,
+    pub source_location: String,
+    pub destination_location: String,
+    pub transport_details: String,
+    pub bump: u8,
+}
+
+impl LegacyUsageRecord {
+    pub const LEN: usize = 8 + // discriminator
+        4 + 32 + // shipment_id
+        4 + LegacyQuotaAccount::MAX_CONCESSION_ID_LEN + // concession_id
+        8 + // amount
+        8 + // timestamp
+        32 + // holder
+        LegacyQualityParameters::LEN + // quality_params
+        4 + 32 + // source_location
+        4 + 32 + // destination_location
+        4 + 64 + // transport_details
+        1; // bump
+
+    /// Convert into the canonical layout
+    pub fn into_current(self) -> UsageRecord {
+        UsageRecord {
+            layout_version: UsageRecord::LAYOUT_VERSION,
+            concession_id: self.concession_id,
+            shipment_id: self.shipment_id,
+            amount: self.amount,
+            timestamp: self.timestamp,
+            holder: self.holder,
+            quality_params: self.quality_params.into(),
+            source_location: self.source_location,
+            destination_location: self.destination_location,
+            transport_details: self.transport_details,
+            bump: self.bump,
+            flagged_by: None,
+            flag_reason: String::new(),
+            flagged_at: 0,
+            quota_account: Pubkey::default(),
+            sequence: UsageRecord::UNSEQUENCED,
+            status: ShipmentStatus::Dispatched,
+            in_transit_at: 0,
+            delivered_at: 0,
+            cancelled_at: 0,
+            rejected_at: 0,
+            payer: self.holder,
+            assay_lab: None,
+            assay_sample_id: String::new(),
+            assayed_params: None,
+            assayed_at: 0,
+            delegate: None,
+            grade_debited: false,
+        }
+    }
+}
+
+/// Quality parameters as laid out by the first release (GCV stored as u16)
+#[derive(AnchorDeserialize)]
+pub struct LegacyQualityParameters {
+    pub gross_calorific_value: u16,
+    pub moisture_content: u16,
+    pub ash_content: u16,
+    pub sulphur_content: u16,
+    pub volatile_matter: u16,
+    pub fixed_carbon: u16,
+    pub coal_grade: LegacyCoalGrade,
+    pub size_classification: String,
+}
+
+impl LegacyQualityParameters {
+    pub const LEN: usize =
+        2 + // gross_calorific_value
+        2 + // moisture_content
+        2 + // ash_content
+        2 + // sulphur_content
+        2 + // volatile_matter
+        2 + // fixed_carbon
+        1 + 1 + // coal_grade (enum + padding)
+        4 + 16; // size_classification
+}
+
+impl From<LegacyQualityParameters> for QualityParameters {
+    fn from(params: LegacyQualityParameters) -> Self {
+        QualityParameters {
+            gross_calorific_value: params.gross_calorific_value.into(),
+            moisture_content: params.moisture_content,
+            ash_content: params.ash_content,
+            sulphur_content: params.sulphur_content,
+            volatile_matter: params.volatile_matter,
+            fixed_carbon: params.fixed_carbon,
+            coal_grade: params.coal_grade.into(),
+            size_classification: params.size_classification,
+        }
+    }
+}
+
+#[derive(AnchorDeserialize)]
+pub enum LegacyQuotaStatus {
+    Active,
+    Suspended,
+    Expired,
+    Exhausted,
+}
+
+impl From<LegacyQuotaStatus> for QuotaStatus {
+    fn from(status: LegacyQuotaStatus) -> Self {
+        // The variant indices differ: the current enum inserts Revoked before
+        // Exhausted, so this has to be an explicit mapping.
+        match status {
+            LegacyQuotaStatus::Active => QuotaStatus::Active,
+            LegacyQuotaStatus::Suspended => QuotaStatus::Suspended,
+            LegacyQuotaStatus::Expired => QuotaStatus::Expired,
+            LegacyQuotaStatus::Exhausted => QuotaStatus::Exhausted,
+        }
+    }
+}
+
+#[derive(AnchorDeserialize)]
+pub enum LegacyQuotaType {
+    Annual,
+    Monthly,
+}
+
+impl From<LegacyQuotaType> for QuotaType {
+    fn from(quota_type: LegacyQuotaType) -> Self {
+        match quota_type {
+            LegacyQuotaType::Annual => QuotaType::Annual,
+            LegacyQuotaType::Monthly => QuotaType::Monthly,
+        }
+    }
+}
+
+#[derive(AnchorDeserialize)]
+pub enum LegacyCoalGrade {
+    GradeA,
+    GradeB,
+    GradeC,
+    GradeD,
+}
+
+impl From<LegacyCoalGrade> for CoalGrade {
+    fn from(grade: LegacyCoalGrade) -> Self {
+        match grade {
+            LegacyCoalGrade::GradeA => CoalGrade::GradeA,
+            LegacyCoalGrade::GradeB => CoalGrade::GradeB,
+            LegacyCoalGrade::GradeC => CoalGrade::GradeC,
+            LegacyCoalGrade::GradeD => CoalGrade::GradeD,
+        }
+    }
+}
@@ -1,3 +1,25 @@
+pub mod clearance;
+pub mod delegate;
+pub mod lab;
+pub mod legacy;
+pub mod program_config;
+pub mod quality_policy;
 pub mod quota_account;
+pub mod rake;
+pub mod reservation;
+pub mod role;
+pub mod tombstone;
+pub mod versioning;
 
-pub use quota_account::*;
\ No newline at end of file
+pub use clearance::*;
+pub use delegate::*;
+pub use lab::*;
+pub use legacy::*;
+pub use program_config::*;
+pub use quality_policy::*;
+pub use quota_account::*;
+pub use rake::*;
+pub use reservation::*;
+pub use role::*;
+pub use tombstone::*;
+pub use versioning::*;
\ No newline at end of file
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+
+/// Singleton program configuration holding the admin authority
+#[account]
+pub struct ProgramConfig {
+    /// Authority allowed to manage the regulator registry
+    pub admin: Pubkey,
+    /// Number of regulator records currently registered
+    pub regulator_count: u32,
+    /// Seconds after dispatch during which a shipment may still be
+    /// cancelled and its tonnage refunded
+    pub cancellation_window: i64,
+    /// Lamports paid from the config account to whoever cranks an expired
+    /// quota, zero to disable
+    pub crank_reward: u64,
+    /// Timestamp when the config was created
+    pub created_at: i64,
+    /// Timestamp when the config was last updated
+    pub updated_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl ProgramConfig {
+    pub const DEFAULT_CANCELLATION_WINDOW: i64 = 24 * 60 * 60;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // admin
+        4 + // regulator_count
+        8 + // cancellation_window
+        8 + // crank_reward
+        8 + // created_at
+        8 + // updated_at
+        1; // bump
+}
+
+/// Registry entry for an authorised regulator
+#[account]
+pub struct RegulatorRecord {
+    /// Public key of the regulator
+    pub regulator: Pubkey,
+    /// Name of the regulating body (e.g. state mining department)
+    pub name: String,
+    /// Jurisdiction the regulator is responsible for (state, coalfield)
+    pub jurisdiction: String,
+    /// Whether the regulator may currently issue quotas
+    pub status: RegulatorStatus,
+    /// Admin who registered the regulator
+    pub added_by: Pubkey,
+    /// Timestamp when the regulator was registered
+    pub added_at: i64,
+    /// Timestamp when the record was last updated
+    pub updated_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl RegulatorRecord {
+    pub const MAX_NAME_LEN: usize = 64;
+    pub const MAX_JURISDICTION_LEN: usize = 64;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // regulator
+        4 + Self::MAX_NAME_LEN + // name
+        4 + Self::MAX_JURISDICTION_LEN + // jurisdiction
+        1 + 1 + // status (enum + padding)
+        32 + // added_by
+        8 + // added_at
+        8 + // updated_at
+        1; // bump
+
+    /// Check if the regulator may currently act
+    pub fn is_active(&self) -> bool {
+        self.status == RegulatorStatus::Active
+    }
+}
+
+// Enums
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum RegulatorStatus {
+    /// Regulator may issue and manage quotas
+    Active,
+    /// Regulator is temporarily barred from issuing quotas
+    Disabled,
+}
+
+// Events
+
+#[event]
+pub struct ProgramConfigInitialized {
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CancellationWindowUpdated {
+    pub old_window: i64,
+    pub new_window: i64,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CrankRewardUpdated {
+    pub old_reward: u64,
+    pub new_reward: u64,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RegulatorAdded {
+    pub regulator: Pubkey,
+    pub name: String,
+    pub jurisdiction: String,
+    pub added_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RegulatorStatusUpdated {
+    pub regulator: Pubkey,
+    pub old_status: RegulatorStatus,
+    pub new_status: RegulatorStatus,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RegulatorRemoved {
+    pub regulator: Pubkey,
+    pub removed_by: Pubkey,
+    pub timestamp: i64,
+}
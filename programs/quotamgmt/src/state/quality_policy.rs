@@ -0,0 +1,232 @@
+use anchor_lang::prelude::*;
+
+use super::{CoalGrade, QualityParameters};
+use crate::errors::QuotaError;
+
+/// Regulator-managed quality limits applied to the shipments of every quota
+/// the policy is assigned to, whether that is a whole region or one quota
+#[account]
+pub struct QualityPolicy {
+    /// Regulator who owns the policy
+    pub regulator: Pubkey,
+    /// Identifier chosen by the regulator, unique per regulator
+    pub policy_id: String,
+    /// Coalfield or region the policy is written for
+    pub region: String,
+    /// Limits enforced on dispatched coal
+    pub bounds: QualityBounds,
+    /// Timestamp when the policy was created
+    pub created_at: i64,
+    /// Timestamp when the policy was last updated
+    pub updated_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl QualityPolicy {
+    pub const MAX_POLICY_ID_LEN: usize = 32;
+    pub const MAX_REGION_LEN: usize = 64;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // regulator
+        4 + Self::MAX_POLICY_ID_LEN + // policy_id
+        4 + Self::MAX_REGION_LEN + // region
+        QualityBounds::LEN + // bounds
+        8 + // created_at
+        8 + // updated_at
+        1; // bump
+}
+
+/// Acceptable ranges for shipment quality parameters. Percentages are scaled
+/// by 100 as in `QualityParameters`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct QualityBounds {
+    /// Minimum Gross Calorific Value in kcal/kg
+    pub min_gcv: u32,
+    /// Maximum Gross Calorific Value in kcal/kg
+    pub max_gcv: u32,
+    /// Maximum moisture content
+    pub max_moisture: u16,
+    /// Maximum ash content
+    pub max_ash: u16,
+    /// Maximum sulphur content
+    pub max_sulphur: u16,
+    /// Minimum volatile matter, unchecked if unset
+    pub min_volatile_matter: Option<u16>,
+    /// Maximum volatile matter, unchecked if unset
+    pub max_volatile_matter: Option<u16>,
+    /// Minimum fixed carbon, unchecked if unset
+    pub min_fixed_carbon: Option<u16>,
+    /// Maximum fixed carbon, unchecked if unset
+    pub max_fixed_carbon: Option<u16>,
+    /// GCV bands the declared grade must agree with, unchecked if empty
+    pub grade_bands: Vec<GradeBand>,
+    /// Allowed deviation of moisture + ash + volatile matter + fixed carbon
+    /// from 100%, unchecked if unset
+    pub proximate_tolerance: Option<u16>,
+}
+
+/// Limits applied to quotas without a quality policy, the ones enforced
+/// before policies were introduced
+impl Default for QualityBounds {
+    fn default() -> Self {
+        Self {
+            min_gcv: 2000,
+            max_gcv: 8000,
+            max_moisture: 5000, // 50.00%
+            max_ash: 5000, // 50.00%
+            max_sulphur: 1000, // 10.00%
+            min_volatile_matter: None,
+            max_volatile_matter: None,
+            min_fixed_carbon: None,
+            max_fixed_carbon: None,
+            grade_bands: Vec::new(),
+            proximate_tolerance: None,
+        }
+    }
+}
+
+impl QualityBounds {
+    /// Largest percentage expressible in the scaled representation
+    pub const MAX_PERCENTAGE: u16 = 10_000;
+    /// One band per `CoalGrade` variant
+    pub const MAX_GRADE_BANDS: usize = 8;
+
+    pub const LEN: usize =
+        4 + // min_gcv
+        4 + // max_gcv
+        2 + // max_moisture
+        2 + // max_ash
+        2 + // max_sulphur
+        1 + 2 + // min_volatile_matter
+        1 + 2 + // max_volatile_matter
+        1 + 2 + // min_fixed_carbon
+        1 + 2 + // max_fixed_carbon
+        4 + Self::MAX_GRADE_BANDS * GradeBand::LEN + // grade_bands
+        1 + 2; // proximate_tolerance
+
+    /// Check the bounds describe non-empty ranges of valid percentages
+    pub fn is_consistent(&self) -> bool {
+        let range_ok = |min: Option<u16>, max: Option<u16>| match (min, max) {
+            (Some(min), Some(max)) => min <= max && max <= Self::MAX_PERCENTAGE,
+            (None, Some(max)) => max <= Self::MAX_PERCENTAGE,
+            (Some(min), None) => min <= Self::MAX_PERCENTAGE,
+            (None, None) => true,
+        };
+
+        self.min_gcv <= self.max_gcv
+            && self.max_moisture <= Self::MAX_PERCENTAGE
+            && self.max_ash <= Self::MAX_PERCENTAGE
+            && self.max_sulphur <= Self::MAX_PERCENTAGE
+            && range_ok(self.min_volatile_matter, self.max_volatile_matter)
+            && range_ok(self.min_fixed_carbon, self.max_fixed_carbon)
+            && self.grade_bands.len() <= Self::MAX_GRADE_BANDS
+            && self.grade_bands.iter().all(|band| band.min_gcv <= band.max_gcv)
+            && self
+                .proximate_tolerance
+                .is_none_or(|tolerance| tolerance <= Self::MAX_PERCENTAGE)
+    }
+
+    /// Ensure the shipment's quality parameters fall within the bounds
+    pub fn check(&self, params: &QualityParameters) -> Result<()> {
+        require!(
+            params.gross_calorific_value >= self.min_gcv
+                && params.gross_calorific_value <= self.max_gcv,
+            QuotaError::InvalidGCVValue
+        );
+        require!(
+            params.moisture_content <= self.max_moisture,
+            QuotaError::InvalidMoistureContent
+        );
+        require!(params.ash_content <= self.max_ash, QuotaError::InvalidAshContent);
+        require!(
+            params.sulphur_content <= self.max_sulphur,
+            QuotaError::InvalidSulphurContent
+        );
+        require!(
+            self.min_volatile_matter.is_none_or(|min| params.volatile_matter >= min)
+                && self.max_volatile_matter.is_none_or(|max| params.volatile_matter <= max),
+            QuotaError::InvalidVolatileMatter
+        );
+        require!(
+            self.min_fixed_carbon.is_none_or(|min| params.fixed_carbon >= min)
+                && self.max_fixed_carbon.is_none_or(|max| params.fixed_carbon <= max),
+            QuotaError::InvalidFixedCarbon
+        );
+
+        // The declared grade must match a band covering the GCV
+        if !self.grade_bands.is_empty() {
+            require!(
+                self.grade_bands.iter().any(|band| band.grade == params.coal_grade
+                    && band.contains(params.gross_calorific_value)),
+                QuotaError::InvalidCoalGrade
+            );
+        }
+
+        // Proximate analysis components should account for the whole sample
+        if let Some(tolerance) = self.proximate_tolerance {
+            let total = params.moisture_content as u32
+                + params.ash_content as u32
+                + params.volatile_matter as u32
+                + params.fixed_carbon as u32;
+            require!(
+                total.abs_diff(Self::MAX_PERCENTAGE as u32) <= tolerance as u32,
+                QuotaError::InvalidQualityParameters
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// GCV range, inclusive at both ends, corresponding to a coal grade
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct GradeBand {
+    /// Grade coal in this band must be declared as
+    pub grade: CoalGrade,
+    /// Lowest GCV in the band (kcal/kg)
+    pub min_gcv: u32,
+    /// Highest GCV in the band (kcal/kg)
+    pub max_gcv: u32,
+}
+
+impl GradeBand {
+    pub const LEN: usize =
+        1 + 1 + // grade (enum + padding)
+        4 + // min_gcv
+        4; // max_gcv
+
+    /// Check if `gcv` falls within the band
+    pub fn contains(&self, gcv: u32) -> bool {
+        gcv >= self.min_gcv && gcv <= self.max_gcv
+    }
+}
+
+// Events
+
+#[event]
+pub struct QualityPolicyCreated {
+    pub regulator: Pubkey,
+    pub policy_id: String,
+    pub region: String,
+    pub bounds: QualityBounds,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct QualityPolicyUpdated {
+    pub regulator: Pubkey,
+    pub policy_id: String,
+    pub old_bounds: QualityBounds,
+    pub new_bounds: QualityBounds,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct QualityPolicyAssigned {
+    pub concession_id: String,
+    pub policy: Pubkey,
+    pub policy_id: String,
+    pub assigned_by: Pubkey,
+    pub timestamp: i64,
+}
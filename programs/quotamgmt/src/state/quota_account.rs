@@ -1,8 +1,17 @@
 use anchor_lang::prelude::*;
 
+use super::Role;
+use crate::errors::QuotaError;
+
 /// Main quota account storing concession quota information
+///
+/// Fields added after the first versioned layout must be appended after
+/// `bump` and decoded in `QuotaAccount::decode_stored` under the layout
+/// version that introduced them.
 #[account]
 pub struct QuotaAccount {
+    /// Layout version of the stored data
+    pub layout_version: u8,
     /// Unique identifier for the mining concession (max 32 chars)
     pub concession_id: String,
     /// Public key of the quota holder
@@ -31,14 +40,64 @@ pub struct QuotaAccount {
     pub updated_at: i64,
     /// PDA bump seed
     pub bump: u8,
+    /// Number of transfers requested from this quota, seeds the next
+    /// `TransferRecord` (layout v2)
+    pub transfer_count: u64,
+    /// Number of usage records created against this quota, seeds the next
+    /// `UsageRecord` (layout v2)
+    pub usage_count: u64,
+    /// Regulator who revoked the quota, if revoked (layout v3)
+    pub revoked_by: Option<Pubkey>,
+    /// Timestamp when the quota was revoked (layout v3)
+    pub revoked_at: i64,
+    /// Reason code given for the revocation (layout v3)
+    pub revocation_reason: Option<RevocationReason>,
+    /// Shipments dispatched but not yet delivered, cancelled or rejected
+    /// (layout v4)
+    pub open_shipments: u64,
+    /// Quality policy enforced on the quota's shipments, if assigned
+    /// (layout v5)
+    pub quality_policy: Option<Pubkey>,
+    /// Tonnage sanctioned per coal grade; grades are unrestricted while
+    /// empty (layout v6)
+    pub grade_allocations: Vec<GradeAllocation>,
+    /// Utilization percentages, in ascending order, at which
+    /// `UtilizationThresholdCrossed` is emitted (layout v7)
+    pub warning_thresholds: Vec<u8>,
+    /// Highest warning threshold already reported, so each is emitted only
+    /// the first time it is crossed (layout v7)
+    pub highest_threshold_crossed: u8,
+    /// Utilization percentage past which dispatches are rejected, if the
+    /// regulator set one (layout v7)
+    pub utilization_cap: Option<u8>,
+    /// Regulator-set ceilings on dispatches per day and week (layout v8)
+    pub dispatch_limits: DispatchLimits,
+    /// Dispatches counted in the trailing day and week (layout v10; calendar
+    /// day and week counters in v8 and v9)
+    pub dispatch_window: DispatchWindow,
+    /// Tonnage held by open reservations, already excluded from
+    /// `available_quota` (layout v9)
+    pub reserved_quota: u64,
+    /// Number of reservations made against this quota, seeds the next
+    /// `Reservation` (layout v9)
+    pub reservation_count: u64,
+    /// Transfers requested from this quota and not yet executed, rejected
+    /// or cancelled (layout v11)
+    pub open_transfers: u64,
 }
 
 impl QuotaAccount {
     pub const MAX_CONCESSION_ID_LEN: usize = 32;
     pub const MAX_MINING_REGION_LEN: usize = 64;
     pub const MAX_ENV_CLEARANCE_LEN: usize = 64;
+    pub const LAYOUT_VERSION: u8 = 11;
+    /// One sub-allocation per `CoalGrade` variant
+    pub const MAX_GRADE_ALLOCATIONS: usize = 8;
+    pub const MAX_WARNING_THRESHOLDS: usize = 4;
+    pub const DEFAULT_WARNING_THRESHOLDS: [u8; 2] = [80, 95];
     
     pub const LEN: usize = 8 + // discriminator
+        1 + // layout_version
         4 + Self::MAX_CONCESSION_ID_LEN + // concession_id
         32 + // holder
         32 + // regulator
@@ -52,7 +111,23 @@ impl QuotaAccount {
         4 + Self::MAX_ENV_CLEARANCE_LEN + // environmental_clearance
         8 + // created_at
         8 + // updated_at
-        1; // bump
+        1 + // bump
+        8 + // transfer_count
+        8 + // usage_count
+        1 + 32 + // revoked_by
+        8 + // revoked_at
+        1 + 1 + 1 + // revocation_reason (option + enum + padding)
+        8 + // open_shipments
+        1 + 32 + // quality_policy
+        4 + Self::MAX_GRADE_ALLOCATIONS * GradeAllocation::LEN + // grade_allocations
+        4 + Self::MAX_WARNING_THRESHOLDS + // warning_thresholds
+        1 + // highest_threshold_crossed
+        1 + 1 + // utilization_cap
+        DispatchLimits::LEN + // dispatch_limits
+        DispatchWindow::LEN + // dispatch_window
+        8 + // reserved_quota
+        8 + // reservation_count
+        8; // open_transfers
 
     /// Check if quota is valid and active
     pub fn is_valid(&self) -> bool {
@@ -72,11 +147,162 @@ impl QuotaAccount {
         }
         ((self.used_quota as f64 / self.allocated_quota as f64) * 100.0) as u8
     }
+
+    /// Check that using `amount` more keeps utilization within the cap
+    pub fn within_utilization_cap(&self, amount: u64) -> bool {
+        self.utilization_cap.is_none_or(|cap| {
+            (self.used_quota as u128 + amount as u128) * 100
+                <= cap as u128 * self.allocated_quota as u128
+        })
+    }
+
+    /// Count a dispatch of `amount` against the trailing day and week,
+    /// rejecting it if any of the regulator's limits would be exceeded
+    pub fn record_dispatch(&mut self, amount: u64, now: i64) -> Result<()> {
+        let window = &mut self.dispatch_window;
+        window.advance(now);
+
+        let limits = &self.dispatch_limits;
+        require!(
+            limits
+                .max_daily_tonnage
+                .is_none_or(|max| window.daily_tonnage() + amount <= max)
+                && limits
+                    .max_weekly_tonnage
+                    .is_none_or(|max| window.weekly_tonnage() + amount <= max)
+                && limits
+                    .max_daily_shipments
+                    .is_none_or(|max| window.daily_shipments() < max),
+            QuotaError::DispatchRateLimitExceeded
+        );
+
+        window.add(amount);
+
+        Ok(())
+    }
+
+    /// Mark warning thresholds reached by the current utilization as
+    /// reported, returning those crossed for the first time
+    pub fn cross_warning_thresholds(&mut self) -> Vec<u8> {
+        let utilization = self.utilization_percentage();
+        let crossed: Vec<u8> = self
+            .warning_thresholds
+            .iter()
+            .copied()
+            .filter(|&threshold| threshold > self.highest_threshold_crossed && threshold <= utilization)
+            .collect();
+        if let Some(&highest) = crossed.last() {
+            self.highest_threshold_crossed = highest;
+        }
+        crossed
+    }
+
+    /// Record that a shipment reached a final stage. Shipments of a migrated
+    /// quota that predate the count are only added once their records are
+    /// migrated, so settling one before that would otherwise underflow.
+    pub fn settle_shipment(&mut self) {
+        self.open_shipments = self.open_shipments.saturating_sub(1);
+    }
+
+    /// Record that a transfer from this quota was executed, rejected or
+    /// cancelled. Transfers requested before the count was kept (quota
+    /// layouts before v11) were never added, so this saturates as well.
+    pub fn settle_transfer(&mut self) {
+        self.open_transfers = self.open_transfers.saturating_sub(1);
+    }
+
+    /// Charge `amount` to the sub-allocation for `grade`, returning its
+    /// remaining tonnage, or `None` when the quota has no grade split
+    pub fn debit_grade(&mut self, grade: &CoalGrade, amount: u64) -> Result<Option<u64>> {
+        if self.grade_allocations.is_empty() {
+            return Ok(None);
+        }
+
+        let allocation = self
+            .grade_allocations
+            .iter_mut()
+            .find(|allocation| &allocation.grade == grade)
+            .ok_or(QuotaError::GradeNotAllocated)?;
+        require!(
+            allocation.available >= amount,
+            QuotaError::InsufficientGradeQuota
+        );
+        allocation.used += amount;
+        allocation.available -= amount;
+
+        Ok(Some(allocation.available))
+    }
+
+    /// Tonnage sanctioned across all grade sub-allocations, a floor for
+    /// `allocated_quota`
+    pub fn grade_allocated_total(&self) -> u64 {
+        self.grade_allocations
+            .iter()
+            .map(|allocation| allocation.allocated)
+            .sum()
+    }
+
+    /// Return `amount` debited by `debit_grade` to the sub-allocation for
+    /// `grade`, if there still is one. Availability is recomputed rather
+    /// than credited, as the sub-allocation may have been reset since.
+    pub fn credit_grade(&mut self, grade: &CoalGrade, amount: u64) {
+        if let Some(allocation) = self
+            .grade_allocations
+            .iter_mut()
+            .find(|allocation| &allocation.grade == grade)
+        {
+            allocation.used = allocation.used.saturating_sub(amount);
+            allocation.available = allocation.allocated.saturating_sub(allocation.used);
+        }
+    }
+
+    /// Move `amount` from the available quota into a reservation
+    pub fn hold(&mut self, amount: u64) -> Result<()> {
+        require!(self.available_quota >= amount, QuotaError::InsufficientQuota);
+        self.available_quota -= amount;
+        self.reserved_quota += amount;
+        Ok(())
+    }
+
+    /// Return a reservation's `amount` to the available quota. Availability
+    /// is recomputed rather than credited, since the allocation may have
+    /// been cut below the outstanding holds while the tonnage was reserved.
+    /// A revoked quota's availability stays frozen and the hold just lapses.
+    pub fn release_hold(&mut self, amount: u64) {
+        self.reserved_quota = self.reserved_quota.saturating_sub(amount);
+        if self.status != QuotaStatus::Revoked {
+            self.available_quota = self
+                .allocated_quota
+                .saturating_sub(self.used_quota + self.reserved_quota);
+        }
+    }
+
+    /// Give back `amount` used by a cancelled shipment. As for
+    /// `release_hold`, availability is recomputed rather than credited, in
+    /// case the allocation was cut below what is used and held since.
+    pub fn refund_usage(&mut self, amount: u64) {
+        self.used_quota = self.used_quota.saturating_sub(amount);
+        self.available_quota = self
+            .allocated_quota
+            .saturating_sub(self.used_quota + self.reserved_quota);
+    }
+
+    /// Whether the quota may be closed and its rent reclaimed
+    pub fn is_closable(&self) -> bool {
+        matches!(self.status, QuotaStatus::Expired | QuotaStatus::Revoked)
+            && self.open_shipments == 0
+            && self.reserved_quota == 0
+            && self.open_transfers == 0
+    }
 }
 
 /// Record of quota usage for each shipment
+///
+/// Follows the same append-only layout rule as `QuotaAccount`.
 #[account]
 pub struct UsageRecord {
+    /// Layout version of the stored data
+    pub layout_version: u8,
     /// Concession ID this usage belongs to
     pub concession_id: String,
     /// Unique shipment identifier
@@ -97,15 +323,68 @@ pub struct UsageRecord {
     pub transport_details: String,
     /// PDA bump seed
     pub bump: u8,
+    /// Inspector or auditor who flagged the shipment, if any (layout v2)
+    pub flagged_by: Option<Pubkey>,
+    /// Reason given when the shipment was flagged (layout v2)
+    pub flag_reason: String,
+    /// Timestamp when the shipment was flagged (layout v2)
+    pub flagged_at: i64,
+    /// Quota account the usage was charged to (layout v3)
+    pub quota_account: Pubkey,
+    /// Position in the quota's usage history, `UNSEQUENCED` for records
+    /// addressed by shipment ID before sequencing (layout v3)
+    pub sequence: u64,
+    /// Current stage of the shipment (layout v4)
+    pub status: ShipmentStatus,
+    /// Timestamp when the shipment left the loading point (layout v4)
+    pub in_transit_at: i64,
+    /// Timestamp when delivery was confirmed (layout v4)
+    pub delivered_at: i64,
+    /// Timestamp when the shipment was cancelled (layout v4)
+    pub cancelled_at: i64,
+    /// Timestamp when the shipment was rejected (layout v4)
+    pub rejected_at: i64,
+    /// Account that paid the record's rent and gets it back on close
+    /// (layout v5)
+    pub payer: Pubkey,
+    /// Accredited lab whose signed assay is attached, if any (layout v6)
+    pub assay_lab: Option<Pubkey>,
+    /// Lab's identifier for the assayed sample (layout v6)
+    pub assay_sample_id: String,
+    /// Quality parameters measured by the lab (layout v6)
+    pub assayed_params: Option<QualityParameters>,
+    /// Timestamp when the assay was attached (layout v6)
+    pub assayed_at: i64,
+    /// Delegate account whose allowance the shipment was charged to, if
+    /// dispatched by a delegate (layout v7)
+    pub delegate: Option<Pubkey>,
+    /// Whether the tonnage was charged to a grade sub-allocation, which only
+    /// then has it credited back on cancellation (layout v8)
+    pub grade_debited: bool,
 }
 
 impl UsageRecord {
-    pub const MAX_CONCESSION_ID_LEN: usize = 32;
+    pub const MAX_CONCESSION_ID_LEN: usize = QuotaAccount::MAX_CONCESSION_ID_LEN;
     pub const MAX_SHIPMENT_ID_LEN: usize = 32;
     pub const MAX_LOCATION_LEN: usize = 100;
     pub const MAX_TRANSPORT_DETAILS_LEN: usize = 200;
+    pub const MAX_FLAG_REASON_LEN: usize = 200;
+    pub const MAX_SAMPLE_ID_LEN: usize = 32;
+    pub const LAYOUT_VERSION: u8 = 8;
+    pub const UNSEQUENCED: u64 = u64::MAX;
+    /// Seconds after dispatch during which shipment details may be edited
+    pub const DETAILS_EDIT_WINDOW: i64 = 72 * 60 * 60;
+    /// Seconds a finalised record is kept before it may be closed
+    pub const RETENTION_PERIOD: i64 = 90 * 24 * 60 * 60;
+    /// Largest GCV difference (kcal/kg) between declared and assayed quality
+    /// that is not treated as a mismatch
+    pub const ASSAY_GCV_TOLERANCE: u32 = 100;
+    /// Largest difference in any percentage (scaled by 100) between declared
+    /// and assayed quality that is not treated as a mismatch
+    pub const ASSAY_PERCENTAGE_TOLERANCE: u16 = 100;
     
     pub const LEN: usize = 8 + // discriminator
+        1 + // layout_version
         4 + Self::MAX_CONCESSION_ID_LEN + // concession_id
         4 + Self::MAX_SHIPMENT_ID_LEN + // shipment_id
         8 + // amount
@@ -115,7 +394,53 @@ impl UsageRecord {
         4 + Self::MAX_LOCATION_LEN + // source_location
         4 + Self::MAX_LOCATION_LEN + // destination_location
         4 + Self::MAX_TRANSPORT_DETAILS_LEN + // transport_details
-        1; // bump
+        1 + // bump
+        1 + 32 + // flagged_by
+        4 + Self::MAX_FLAG_REASON_LEN + // flag_reason
+        8 + // flagged_at
+        32 + // quota_account
+        8 + // sequence
+        1 + 1 + // status (enum + padding)
+        8 + // in_transit_at
+        8 + // delivered_at
+        8 + // cancelled_at
+        8 + // rejected_at
+        32 + // payer
+        1 + 32 + // assay_lab
+        4 + Self::MAX_SAMPLE_ID_LEN + // assay_sample_id
+        1 + QualityParameters::LEN + // assayed_params
+        8 + // assayed_at
+        1 + 32 + // delegate
+        1; // grade_debited
+
+    /// Move the shipment to `new_status`, stamping the stage timestamp
+    pub fn transition(&mut self, new_status: ShipmentStatus, now: i64) -> Result<()> {
+        require!(
+            self.status.can_transition_to(&new_status),
+            QuotaError::InvalidShipmentTransition
+        );
+
+        match new_status {
+            ShipmentStatus::Dispatched => {}
+            ShipmentStatus::InTransit => self.in_transit_at = now,
+            ShipmentStatus::Delivered => self.delivered_at = now,
+            ShipmentStatus::Cancelled => self.cancelled_at = now,
+            ShipmentStatus::Rejected => self.rejected_at = now,
+        }
+        self.status = new_status;
+
+        Ok(())
+    }
+
+    /// Timestamp when the shipment reached its final stage, if it has
+    pub fn finalised_at(&self) -> Option<i64> {
+        match self.status {
+            ShipmentStatus::Delivered => Some(self.delivered_at),
+            ShipmentStatus::Cancelled => Some(self.cancelled_at),
+            ShipmentStatus::Rejected => Some(self.rejected_at),
+            ShipmentStatus::Dispatched | ShipmentStatus::InTransit => None,
+        }
+    }
 }
 
 /// Quality parameters for coal shipments
@@ -151,20 +476,153 @@ impl QualityParameters {
         2 + // fixed_carbon
         1 + 1 + // coal_grade (enum + padding)
         4 + Self::MAX_SIZE_CLASSIFICATION_LEN; // size_classification
+
+    /// Check if `other` describes the same coal, allowing for measurement
+    /// differences up to the given tolerances
+    pub fn agrees_with(&self, other: &QualityParameters, gcv_tolerance: u32, percentage_tolerance: u16) -> bool {
+        let close = |a: u16, b: u16| a.abs_diff(b) <= percentage_tolerance;
+
+        self.coal_grade == other.coal_grade
+            && self.gross_calorific_value.abs_diff(other.gross_calorific_value) <= gcv_tolerance
+            && close(self.moisture_content, other.moisture_content)
+            && close(self.ash_content, other.ash_content)
+            && close(self.sulphur_content, other.sulphur_content)
+            && close(self.volatile_matter, other.volatile_matter)
+            && close(self.fixed_carbon, other.fixed_carbon)
+    }
 }
 
-/// Transfer record for quota transfers between concessions
+/// Tonnage sanctioned for one coal grade within a concession
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct GradeAllocation {
+    /// Grade the tonnage is sanctioned for
+    pub grade: CoalGrade,
+    /// Tonnage sanctioned for the grade
+    pub allocated: u64,
+    /// Tonnage of the grade dispatched so far
+    pub used: u64,
+    /// Tonnage of the grade still available
+    pub available: u64,
+}
+
+impl GradeAllocation {
+    pub const LEN: usize =
+        1 + 1 + // grade (enum + padding)
+        8 + // allocated
+        8 + // used
+        8; // available
+}
+
+/// Ceilings on how fast a concession may dispatch; `None` leaves that
+/// window unlimited
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct DispatchLimits {
+    /// Maximum tonnage in any 24 hours
+    pub max_daily_tonnage: Option<u64>,
+    /// Maximum tonnage in any 7 days
+    pub max_weekly_tonnage: Option<u64>,
+    /// Maximum number of shipments in any 24 hours
+    pub max_daily_shipments: Option<u32>,
+}
+
+impl DispatchLimits {
+    pub const LEN: usize =
+        1 + 8 + // max_daily_tonnage
+        1 + 8 + // max_weekly_tonnage
+        1 + 4; // max_daily_shipments
+}
+
+/// Dispatches in the trailing day and week, kept in ring buffers of hourly
+/// and daily buckets. One bucket more than the period is kept, so every span
+/// of 24 hours or 7 days lies within the counted buckets and a limit cannot
+/// be doubled up across a bucket boundary.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct DispatchWindow {
+    /// Unix hour of the newest hourly bucket
+    pub latest_hour: i64,
+    /// Tonnage dispatched per hour, indexed by unix hour modulo
+    /// `HOURLY_BUCKETS`
+    pub hourly_tonnage: [u64; 25],
+    /// Shipments dispatched per hour, indexed like `hourly_tonnage`
+    pub hourly_shipments: [u32; 25],
+    /// Unix day of the newest daily bucket
+    pub latest_day: i64,
+    /// Tonnage dispatched per UTC day, indexed by unix day modulo
+    /// `DAILY_BUCKETS`
+    pub daily_tonnage: [u64; 8],
+}
+
+impl DispatchWindow {
+    pub const HOURLY_BUCKETS: usize = 25;
+    pub const DAILY_BUCKETS: usize = 8;
+    pub const SECONDS_PER_HOUR: i64 = 3_600;
+    pub const SECONDS_PER_DAY: i64 = 86_400;
+
+    pub const LEN: usize =
+        8 + // latest_hour
+        Self::HOURLY_BUCKETS * 8 + // hourly_tonnage
+        Self::HOURLY_BUCKETS * 4 + // hourly_shipments
+        8 + // latest_day
+        Self::DAILY_BUCKETS * 8; // daily_tonnage
+
+    /// Move the windows forward to `now`, clearing buckets that have aged out
+    pub fn advance(&mut self, now: i64) {
+        let hour = now.div_euclid(Self::SECONDS_PER_HOUR);
+        let elapsed_hours = (hour - self.latest_hour).clamp(0, Self::HOURLY_BUCKETS as i64);
+        for step in 1..=elapsed_hours {
+            let bucket = (self.latest_hour + step).rem_euclid(Self::HOURLY_BUCKETS as i64) as usize;
+            self.hourly_tonnage[bucket] = 0;
+            self.hourly_shipments[bucket] = 0;
+        }
+        self.latest_hour = self.latest_hour.max(hour);
+
+        let day = now.div_euclid(Self::SECONDS_PER_DAY);
+        let elapsed_days = (day - self.latest_day).clamp(0, Self::DAILY_BUCKETS as i64);
+        for step in 1..=elapsed_days {
+            let bucket = (self.latest_day + step).rem_euclid(Self::DAILY_BUCKETS as i64) as usize;
+            self.daily_tonnage[bucket] = 0;
+        }
+        self.latest_day = self.latest_day.max(day);
+    }
+
+    /// Tonnage dispatched in the trailing day
+    pub fn daily_tonnage(&self) -> u64 {
+        self.hourly_tonnage.iter().sum()
+    }
+
+    /// Shipments dispatched in the trailing day
+    pub fn daily_shipments(&self) -> u32 {
+        self.hourly_shipments.iter().sum()
+    }
+
+    /// Tonnage dispatched in the trailing week
+    pub fn weekly_tonnage(&self) -> u64 {
+        self.daily_tonnage.iter().sum()
+    }
+
+    /// Count a dispatch of `amount` in the newest buckets; `advance` must
+    /// have been called first
+    pub fn add(&mut self, amount: u64) {
+        let hour = self.latest_hour.rem_euclid(Self::HOURLY_BUCKETS as i64) as usize;
+        let day = self.latest_day.rem_euclid(Self::DAILY_BUCKETS as i64) as usize;
+        self.hourly_tonnage[hour] += amount;
+        self.hourly_shipments[hour] += 1;
+        self.daily_tonnage[day] += amount;
+    }
+}
+
+/// Transfer record tracking a quota transfer from request to execution
 #[account]
 pub struct TransferRecord {
     /// Source concession ID
     pub from_concession: String,
     /// Destination concession ID
     pub to_concession: String,
-    /// Amount transferred
+    /// Amount to transfer
     pub amount: u64,
-    /// Transfer timestamp
+    /// Timestamp when the transfer was requested
     pub timestamp: i64,
-    /// Authorized by (regulator or holder)
+    /// Regulator who approved or rejected the transfer
     pub authorized_by: Pubkey,
     /// Transfer reason/description
     pub transfer_reason: String,
@@ -172,10 +630,28 @@ pub struct TransferRecord {
     pub transfer_type: TransferType,
     /// PDA bump seed
     pub bump: u8,
+    /// Source quota account
+    pub from_quota: Pubkey,
+    /// Destination quota account
+    pub to_quota: Pubkey,
+    /// Source holder who requested the transfer
+    pub requested_by: Pubkey,
+    /// Current stage of the transfer
+    pub status: TransferStatus,
+    /// Timestamp when the regulator approved or rejected the transfer
+    pub decided_at: i64,
+    /// Timestamp when the recipient accepted the transfer
+    pub accepted_at: i64,
+    /// Timestamp when the tonnage was moved
+    pub executed_at: i64,
+    /// Reason given by the regulator when rejecting
+    pub rejection_reason: String,
+    /// Position in the source quota's transfer history
+    pub sequence: u64,
 }
 
 impl TransferRecord {
-    pub const MAX_CONCESSION_ID_LEN: usize = 32;
+    pub const MAX_CONCESSION_ID_LEN: usize = QuotaAccount::MAX_CONCESSION_ID_LEN;
     pub const MAX_TRANSFER_REASON_LEN: usize = 200;
     
     pub const LEN: usize = 8 + // discriminator
@@ -186,7 +662,35 @@ impl TransferRecord {
         32 + // authorized_by
         4 + Self::MAX_TRANSFER_REASON_LEN + // transfer_reason
         1 + 1 + // transfer_type (enum + padding)
-        1; // bump
+        1 + // bump
+        32 + // from_quota
+        32 + // to_quota
+        32 + // requested_by
+        1 + 1 + // status (enum + padding)
+        8 + // decided_at
+        8 + // accepted_at
+        8 + // executed_at
+        4 + Self::MAX_TRANSFER_REASON_LEN + // rejection_reason
+        8; // sequence
+
+    /// Seconds a settled record is kept before it may be closed
+    pub const RETENTION_PERIOD: i64 = 90 * 24 * 60 * 60;
+
+    /// Whether the transfer can no longer change stage
+    pub fn is_settled(&self) -> bool {
+        matches!(
+            self.status,
+            TransferStatus::Rejected | TransferStatus::Executed | TransferStatus::Cancelled
+        )
+    }
+
+    /// Timestamp of the latest stage change
+    pub fn last_activity_at(&self) -> i64 {
+        self.timestamp
+            .max(self.decided_at)
+            .max(self.accepted_at)
+            .max(self.executed_at)
+    }
 }
 
 // Enums
@@ -205,6 +709,22 @@ pub enum QuotaStatus {
     Exhausted,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum RevocationReason {
+    /// Breach of environmental clearance conditions
+    EnvironmentalViolation,
+    /// Extraction beyond the sanctioned limits or area
+    IllegalMining,
+    /// Unpaid royalty or statutory dues
+    RoyaltyDefault,
+    /// Underlying clearance or lease withdrawn
+    ClearanceWithdrawn,
+    /// Revocation ordered by a court or tribunal
+    CourtOrder,
+    /// Any other reason, detailed in the revocation event
+    Other,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
 pub enum QuotaType {
     /// Annual quota allocation
@@ -237,6 +757,59 @@ pub enum CoalGrade {
     PrimeCoking,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum TransferStatus {
+    /// Requested by the source holder, awaiting the regulator
+    Requested,
+    /// Approved by the regulator, awaiting the recipient
+    Approved,
+    /// Rejected by the regulator
+    Rejected,
+    /// Accepted by the recipient, ready to execute
+    Accepted,
+    /// Tonnage has been moved
+    Executed,
+    /// Withdrawn by the source holder before execution
+    Cancelled,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum ShipmentStatus {
+    /// Quota debited, shipment waiting at the loading point
+    Dispatched,
+    /// Shipment has left the loading point
+    InTransit,
+    /// Shipment received at the destination
+    Delivered,
+    /// Shipment withdrawn before delivery, tonnage refunded to the quota
+    Cancelled,
+    /// Shipment refused by an inspector or the regulator
+    Rejected,
+}
+
+impl ShipmentStatus {
+    /// Check if the shipment can move from this stage to `next`
+    pub fn can_transition_to(&self, next: &ShipmentStatus) -> bool {
+        matches!(
+            (self, next),
+            (ShipmentStatus::Dispatched, ShipmentStatus::InTransit)
+                | (ShipmentStatus::Dispatched, ShipmentStatus::Cancelled)
+                | (ShipmentStatus::Dispatched, ShipmentStatus::Rejected)
+                | (ShipmentStatus::InTransit, ShipmentStatus::Delivered)
+                | (ShipmentStatus::InTransit, ShipmentStatus::Cancelled)
+                | (ShipmentStatus::InTransit, ShipmentStatus::Rejected)
+        )
+    }
+
+    /// Check if the shipment has reached a terminal stage
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            ShipmentStatus::Delivered | ShipmentStatus::Cancelled | ShipmentStatus::Rejected
+        )
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
 pub enum TransferType {
     /// Emergency transfer due to operational needs
@@ -259,18 +832,42 @@ pub struct QuotaInitialized {
     pub validity_period: i64,
     pub quota_type: QuotaType,
     pub mining_region: String,
+    pub environmental_clearance: String,
 }
 
 #[event]
 pub struct QuotaUsed {
     pub concession_id: String,
     pub shipment_id: String,
+    pub sequence: u64,
     pub amount: u64,
     pub remaining_quota: u64,
+    pub remaining_grade_quota: Option<u64>,
     pub quality_params: QualityParameters,
     pub timestamp: i64,
 }
 
+#[event]
+pub struct TransferRequested {
+    pub from_concession: String,
+    pub to_concession: String,
+    pub sequence: u64,
+    pub amount: u64,
+    pub transfer_type: TransferType,
+    pub requested_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransferStatusUpdated {
+    pub from_concession: String,
+    pub to_concession: String,
+    pub old_status: TransferStatus,
+    pub new_status: TransferStatus,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct QuotaTransferred {
     pub from_concession: String,
@@ -291,6 +888,76 @@ pub struct QuotaStatusUpdated {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct QuotaMetadataUpdated {
+    pub concession_id: String,
+    pub old_mining_region: String,
+    pub new_mining_region: String,
+    pub old_environmental_clearance: String,
+    pub new_environmental_clearance: String,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GradeAllocationUpdated {
+    pub concession_id: String,
+    pub grade: CoalGrade,
+    pub old_allocated: u64,
+    pub new_allocated: u64,
+    pub used: u64,
+    pub available: u64,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct QuotaBatchUsed {
+    pub concession_id: String,
+    pub first_sequence: u64,
+    pub shipment_count: u64,
+    pub total_amount: u64,
+    pub remaining_quota: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UtilizationThresholdsUpdated {
+    pub concession_id: String,
+    pub warning_thresholds: Vec<u8>,
+    pub utilization_cap: Option<u8>,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DispatchLimitsUpdated {
+    pub concession_id: String,
+    pub dispatch_limits: DispatchLimits,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UtilizationThresholdCrossed {
+    pub concession_id: String,
+    pub threshold: u8,
+    pub utilization: u8,
+    pub used_quota: u64,
+    pub allocated_quota: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct QuotaRevoked {
+    pub concession_id: String,
+    pub reason_code: RevocationReason,
+    pub details: String,
+    pub frozen_quota: u64,
+    pub revoked_by: Pubkey,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct QuotaUpdated {
     pub concession_id: String,
@@ -300,4 +967,103 @@ pub struct QuotaUpdated {
     pub new_validity_period: i64,
     pub updated_by: Pubkey,
     pub timestamp: i64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub old_layout_version: u8,
+    pub new_layout_version: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ShipmentFlagged {
+    pub concession_id: String,
+    pub shipment_id: String,
+    pub flagged_by: Pubkey,
+    pub role: Option<Role>,
+    pub reason: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ShipmentDetailsUpdated {
+    pub concession_id: String,
+    pub shipment_id: String,
+    pub sequence: u64,
+    pub source_location: String,
+    pub destination_location: String,
+    pub transport_details: String,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ShipmentStatusUpdated {
+    pub concession_id: String,
+    pub shipment_id: String,
+    pub sequence: u64,
+    pub old_status: ShipmentStatus,
+    pub new_status: ShipmentStatus,
+    pub updated_by: Pubkey,
+    pub reason: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ShipmentCancelled {
+    pub concession_id: String,
+    pub shipment_id: String,
+    pub sequence: u64,
+    pub refunded_amount: u64,
+    pub available_quota: u64,
+    pub coal_grade: CoalGrade,
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct QuotaArchived {
+    pub concession_id: String,
+    pub holder: Pubkey,
+    pub allocated_quota: u64,
+    pub used_quota: u64,
+    pub available_quota: u64,
+    pub status: QuotaStatus,
+    pub closed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UsageRecordArchived {
+    pub quota_account: Pubkey,
+    pub concession_id: String,
+    pub shipment_id: String,
+    pub sequence: u64,
+    pub amount: u64,
+    pub quality_params: QualityParameters,
+    pub source_location: String,
+    pub destination_location: String,
+    pub transport_details: String,
+    pub status: ShipmentStatus,
+    pub dispatched_at: i64,
+    pub finalised_at: i64,
+    pub flagged_by: Option<Pubkey>,
+    pub closed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransferRecordArchived {
+    pub from_quota: Pubkey,
+    pub to_quota: Pubkey,
+    pub sequence: u64,
+    pub amount: u64,
+    pub status: TransferStatus,
+    pub authorized_by: Pubkey,
+    pub requested_at: i64,
+    pub executed_at: i64,
+    pub closed_by: Pubkey,
+    pub timestamp: i64,
+}
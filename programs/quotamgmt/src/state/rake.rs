@@ -0,0 +1,172 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use super::UsageRecord;
+
+/// Railway rake dispatched as a single usage against a quota. The wagon
+/// weights are committed to as a Merkle root so individual wagons can be
+/// checked by inspectors later without storing all of them on chain.
+#[account]
+pub struct RakeShipment {
+    /// Quota account the rake was charged to
+    pub quota_account: Pubkey,
+    /// Usage record debiting the rake's total tonnage
+    pub usage_record: Pubkey,
+    /// Railway rake number, also used as the usage record's shipment ID
+    pub rake_number: String,
+    /// Code of the railway siding the rake was loaded at
+    pub siding_code: String,
+    /// Number of wagons in the rake
+    pub wagon_count: u16,
+    /// Declared net weight of all wagons in kilograms
+    pub net_weight_kg: u64,
+    /// Merkle root over the rake's `WagonWeight` leaves
+    pub wagon_root: [u8; 32],
+    /// Bitmap of wagon indices an inspector has verified
+    pub verified_wagons: [u8; 16],
+    /// Number of wagons verified so far
+    pub verified_count: u16,
+    /// Declared net weight of the verified wagons in kilograms
+    pub verified_net_weight_kg: u64,
+    /// Net weight of the verified wagons as re-weighed by inspectors, in
+    /// kilograms
+    pub measured_net_weight_kg: u64,
+    /// Verified wagons whose re-weighed gross differed from the declaration
+    pub discrepancies: u16,
+    /// Holder or dispatch clerk who dispatched the rake and paid its rent
+    pub dispatched_by: Pubkey,
+    /// Timestamp when the rake was dispatched
+    pub created_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl RakeShipment {
+    pub const MAX_SIDING_CODE_LEN: usize = 16;
+    /// One bit per wagon in `verified_wagons`
+    pub const MAX_WAGONS: u16 = 128;
+    /// Allowed difference between declared and re-weighed gross weight
+    pub const WAGON_WEIGHT_TOLERANCE_KG: u64 = 500;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // quota_account
+        32 + // usage_record
+        4 + UsageRecord::MAX_SHIPMENT_ID_LEN + // rake_number
+        4 + Self::MAX_SIDING_CODE_LEN + // siding_code
+        2 + // wagon_count
+        8 + // net_weight_kg
+        32 + // wagon_root
+        16 + // verified_wagons
+        2 + // verified_count
+        8 + // verified_net_weight_kg
+        8 + // measured_net_weight_kg
+        2 + // discrepancies
+        32 + // dispatched_by
+        8 + // created_at
+        1; // bump
+
+    /// Tonnage charged to the quota for a rake, rounded up to whole tonnes
+    pub fn tonnage(net_weight_kg: u64) -> u64 {
+        net_weight_kg.div_ceil(1000)
+    }
+
+    /// Check if the wagon at `index` has already been verified
+    pub fn is_wagon_verified(&self, index: u16) -> bool {
+        self.verified_wagons[index as usize / 8] & (1 << (index % 8)) != 0
+    }
+
+    /// Record the wagon at `index` as verified
+    pub fn mark_wagon_verified(&mut self, index: u16) {
+        self.verified_wagons[index as usize / 8] |= 1 << (index % 8);
+        self.verified_count += 1;
+    }
+
+    /// Whether the verified wagons contradict the declared net weight: they
+    /// already weigh more by their weighbridge entries or as re-weighed, or
+    /// all of them are verified and their entries add up to another total
+    pub fn wagons_disagree_with_declaration(&self) -> bool {
+        self.verified_net_weight_kg > self.net_weight_kg
+            || self.measured_net_weight_kg > self.net_weight_kg
+            || (self.verified_count == self.wagon_count
+                && self.verified_net_weight_kg != self.net_weight_kg)
+    }
+
+    /// Check that `wagon` is one of the leaves committed to by `wagon_root`.
+    /// Leaves are `sha256(0x00 || borsh(wagon))` and nodes are
+    /// `sha256(0x01 || min(a, b) || max(a, b))`, so proofs need no
+    /// left/right markers.
+    pub fn proves_wagon(&self, wagon: &WagonWeight, proof: &[[u8; 32]]) -> Result<bool> {
+        let leaf = hashv(&[&[0], &wagon.try_to_vec()?]).to_bytes();
+        let root = proof.iter().fold(leaf, |node, sibling| {
+            let (first, second) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+            hashv(&[&[1], &first, &second]).to_bytes()
+        });
+        Ok(root == self.wagon_root)
+    }
+}
+
+/// Weighbridge entry for one wagon of a rake, the leaf of `wagon_root`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct WagonWeight {
+    /// Position of the wagon in the rake, from 0
+    pub index: u16,
+    /// Wagon number painted on the wagon
+    pub wagon_number: String,
+    /// Empty weight of the wagon in kilograms
+    pub tare_weight_kg: u64,
+    /// Loaded weight of the wagon in kilograms
+    pub gross_weight_kg: u64,
+}
+
+impl WagonWeight {
+    /// Net coal weight of the wagon in kilograms
+    pub fn net_weight_kg(&self) -> u64 {
+        self.gross_weight_kg.saturating_sub(self.tare_weight_kg)
+    }
+}
+
+// Events
+
+#[event]
+pub struct RakeDispatched {
+    pub concession_id: String,
+    pub rake_number: String,
+    pub siding_code: String,
+    pub sequence: u64,
+    pub wagon_count: u16,
+    pub net_weight_kg: u64,
+    pub amount: u64,
+    pub wagon_root: [u8; 32],
+    pub dispatched_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WagonVerified {
+    pub concession_id: String,
+    pub rake_number: String,
+    pub index: u16,
+    pub wagon_number: String,
+    pub declared_gross_weight_kg: u64,
+    pub measured_gross_weight_kg: u64,
+    pub matches_declared: bool,
+    pub verified_count: u16,
+    pub verified_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RakeShipmentArchived {
+    pub quota_account: Pubkey,
+    pub usage_record: Pubkey,
+    pub rake_number: String,
+    pub siding_code: String,
+    pub wagon_count: u16,
+    pub net_weight_kg: u64,
+    pub verified_count: u16,
+    pub verified_net_weight_kg: u64,
+    pub measured_net_weight_kg: u64,
+    pub discrepancies: u16,
+    pub closed_by: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+/// Tonnage held back from a quota for a shipment that has not yet been
+/// dispatched, e.g. a truck queued at the gate
+#[account]
+pub struct Reservation {
+    /// Quota account the tonnage is held on
+    pub quota_account: Pubkey,
+    /// Sequence number of the reservation within its quota
+    pub sequence: u64,
+    /// Tonnage held
+    pub amount: u64,
+    /// Gate pass, vehicle number or other planner reference
+    pub reference: String,
+    /// Account that paid for the reservation and reclaims its rent
+    pub payer: Pubkey,
+    /// Unix timestamp after which anyone may release the hold
+    pub expires_at: i64,
+    /// Timestamp when the reservation was made
+    pub created_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl Reservation {
+    pub const MAX_REFERENCE_LEN: usize = 32;
+    /// Longest a hold may be kept before it lapses
+    pub const MAX_RESERVATION_PERIOD: i64 = 7 * 24 * 60 * 60;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // quota_account
+        8 + // sequence
+        8 + // amount
+        4 + Self::MAX_REFERENCE_LEN + // reference
+        32 + // payer
+        8 + // expires_at
+        8 + // created_at
+        1; // bump
+
+    /// Check if the hold has lapsed
+    pub fn is_expired(&self, now: i64) -> bool {
+        now > self.expires_at
+    }
+}
+
+// Events
+
+#[event]
+pub struct QuotaReserved {
+    pub concession_id: String,
+    pub sequence: u64,
+    pub amount: u64,
+    pub reference: String,
+    pub reserved_by: Pubkey,
+    pub expires_at: i64,
+    pub reserved_quota: u64,
+    pub available_quota: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReservationReleased {
+    pub concession_id: String,
+    pub sequence: u64,
+    pub amount: u64,
+    pub expired: bool,
+    pub released_by: Pubkey,
+    pub available_quota: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReservationConsumed {
+    pub concession_id: String,
+    pub sequence: u64,
+    pub shipment_id: String,
+    pub usage_sequence: u64,
+    pub amount: u64,
+    pub consumed_by: Pubkey,
+    pub timestamp: i64,
+}
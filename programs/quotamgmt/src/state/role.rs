@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+/// Scoped role granted to a member of staff on a single quota
+#[account]
+pub struct RoleAssignment {
+    /// Quota account the role applies to
+    pub quota_account: Pubkey,
+    /// Public key holding the role
+    pub member: Pubkey,
+    /// Role granted
+    pub role: Role,
+    /// Holder or regulator who granted the role
+    pub granted_by: Pubkey,
+    /// Timestamp when the role was granted
+    pub granted_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+    /// Account that paid for the assignment and reclaims its rent
+    pub payer: Pubkey,
+}
+
+impl RoleAssignment {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // quota_account
+        32 + // member
+        1 + 1 + // role (enum + padding)
+        32 + // granted_by
+        8 + // granted_at
+        1 + // bump
+        32; // payer
+}
+
+// Enums
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    /// Mine inspector appointed by the regulator
+    Inspector,
+    /// Third-party auditor appointed by the regulator
+    Auditor,
+    /// Site dispatch clerk appointed by the holder
+    DispatchClerk,
+}
+
+// Events
+
+#[event]
+pub struct RoleGranted {
+    pub concession_id: String,
+    pub member: Pubkey,
+    pub role: Role,
+    pub granted_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub concession_id: String,
+    pub member: Pubkey,
+    pub role: Role,
+    pub revoked_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleAssignmentArchived {
+    pub quota_account: Pubkey,
+    pub member: Pubkey,
+    pub role: Role,
+    pub closed_by: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+/// Left at a quota's address once the quota is closed, so that a quota
+/// re-issued to the same holder under the same concession ID numbers its
+/// records after the closed one's rather than colliding with those still
+/// retained
+#[account]
+pub struct QuotaTombstone {
+    /// Quota account address the tombstone belongs to
+    pub quota_account: Pubkey,
+    /// Transfers requested from the quota before it was last closed
+    pub transfer_count: u64,
+    /// Usage records created against the quota before it was last closed
+    pub usage_count: u64,
+    /// Reservations made against the quota before it was last closed
+    pub reservation_count: u64,
+    /// Timestamp when the quota was last closed
+    pub closed_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl QuotaTombstone {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // quota_account
+        8 + // transfer_count
+        8 + // usage_count
+        8 + // reservation_count
+        8 + // closed_at
+        1; // bump
+}
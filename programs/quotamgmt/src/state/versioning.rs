@@ -0,0 +1,342 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use super::{
+    DispatchLimits, DispatchWindow, LegacyQuotaAccount, LegacyUsageRecord, QuotaAccount,
+    ShipmentStatus, UsageRecord,
+};
+use crate::errors::QuotaError;
+
+// Every stored layout of an account type shares one discriminator. Layouts
+// written before `layout_version` existed are told apart by their allocated
+// length; later ones by the version byte that follows the discriminator.
+
+/// Allocated length of a `QuotaAccount` in the unversioned `state` layout
+pub const UNVERSIONED_QUOTA_ACCOUNT_LEN: usize = 297;
+
+/// Allocated length of a `UsageRecord` in the unversioned `state` layout
+pub const UNVERSIONED_USAGE_RECORD_LEN: usize = 581;
+
+impl QuotaAccount {
+    /// Whether the account was written with the current layout, judged from
+    /// its stored data since an older layout with enough trailing space
+    /// still loads as `QuotaAccount`. Layouts that no longer load fail
+    /// earlier with Anchor's `AccountDidNotDeserialize`.
+    pub fn is_current_layout(account: &AccountInfo) -> bool {
+        Self::stored_layout_version(&account.data.borrow()) == Self::LAYOUT_VERSION
+    }
+
+    /// Layout version of stored data, 0 for the layouts predating the field
+    pub fn stored_layout_version(data: &[u8]) -> u8 {
+        match data.len() {
+            LegacyQuotaAccount::LEN | UNVERSIONED_QUOTA_ACCOUNT_LEN => 0,
+            _ => data.get(8).copied().unwrap_or(0),
+        }
+    }
+
+    /// Decode a quota account stored in any known layout into the current one
+    pub fn decode_stored(data: &[u8]) -> Result<Self> {
+        require!(
+            data.starts_with(Self::DISCRIMINATOR),
+            QuotaError::UnrecognizedAccountLayout
+        );
+        let mut body = &data[8..];
+
+        match data.len() {
+            LegacyQuotaAccount::LEN => Ok(LegacyQuotaAccount::deserialize(&mut body)?.into_current()),
+            UNVERSIONED_QUOTA_ACCOUNT_LEN => Self::deserialize_fields(0, &mut body),
+            _ => {
+                let version = u8::deserialize(&mut body)?;
+                require!(
+                    (1..=Self::LAYOUT_VERSION).contains(&version),
+                    QuotaError::UnrecognizedAccountLayout
+                );
+                Self::deserialize_fields(version, &mut body)
+            }
+        }
+    }
+
+    /// Bring a usage record stored before open shipments were counted
+    /// (record layouts before v5) into `open_shipments`. Sequenced records
+    /// were all counted as open when the quota was migrated, so settled ones
+    /// are taken back out; unsequenced ones were not, so open ones are added.
+    pub fn count_legacy_shipment(&mut self, record: &UsageRecord) {
+        let is_open = record.finalised_at().is_none();
+        if record.sequence == UsageRecord::UNSEQUENCED {
+            if is_open {
+                self.open_shipments += 1;
+            }
+        } else if !is_open {
+            self.settle_shipment();
+        }
+    }
+
+    /// Read the fields following `layout_version`, defaulting those newer
+    /// than `version`
+    fn deserialize_fields(version: u8, buf: &mut &[u8]) -> Result<Self> {
+        let mut account = Self {
+            layout_version: Self::LAYOUT_VERSION,
+            concession_id: AnchorDeserialize::deserialize(buf)?,
+            holder: AnchorDeserialize::deserialize(buf)?,
+            regulator: AnchorDeserialize::deserialize(buf)?,
+            allocated_quota: AnchorDeserialize::deserialize(buf)?,
+            used_quota: AnchorDeserialize::deserialize(buf)?,
+            available_quota: AnchorDeserialize::deserialize(buf)?,
+            validity_period: AnchorDeserialize::deserialize(buf)?,
+            status: AnchorDeserialize::deserialize(buf)?,
+            quota_type: AnchorDeserialize::deserialize(buf)?,
+            mining_region: AnchorDeserialize::deserialize(buf)?,
+            environmental_clearance: AnchorDeserialize::deserialize(buf)?,
+            created_at: AnchorDeserialize::deserialize(buf)?,
+            updated_at: AnchorDeserialize::deserialize(buf)?,
+            bump: AnchorDeserialize::deserialize(buf)?,
+            transfer_count: 0,
+            usage_count: 0,
+            revoked_by: None,
+            revoked_at: 0,
+            revocation_reason: None,
+            open_shipments: 0,
+            quality_policy: None,
+            grade_allocations: Vec::new(),
+            warning_thresholds: QuotaAccount::DEFAULT_WARNING_THRESHOLDS.to_vec(),
+            highest_threshold_crossed: 0,
+            utilization_cap: None,
+            dispatch_limits: DispatchLimits::default(),
+            dispatch_window: DispatchWindow::default(),
+            reserved_quota: 0,
+            reservation_count: 0,
+            open_transfers: 0,
+        };
+
+        if version >= 2 {
+            account.transfer_count = AnchorDeserialize::deserialize(buf)?;
+            account.usage_count = AnchorDeserialize::deserialize(buf)?;
+        }
+        if version >= 3 {
+            account.revoked_by = AnchorDeserialize::deserialize(buf)?;
+            account.revoked_at = AnchorDeserialize::deserialize(buf)?;
+            account.revocation_reason = AnchorDeserialize::deserialize(buf)?;
+        }
+        if version >= 4 {
+            account.open_shipments = AnchorDeserialize::deserialize(buf)?;
+        } else {
+            // Nothing was counted yet, so every sequenced shipment is taken as
+            // open until its record is migrated
+            account.open_shipments = account.usage_count;
+        }
+        if version >= 5 {
+            account.quality_policy = AnchorDeserialize::deserialize(buf)?;
+        }
+        if version >= 6 {
+            account.grade_allocations = AnchorDeserialize::deserialize(buf)?;
+        }
+        if version >= 7 {
+            account.warning_thresholds = AnchorDeserialize::deserialize(buf)?;
+            account.highest_threshold_crossed = AnchorDeserialize::deserialize(buf)?;
+            account.utilization_cap = AnchorDeserialize::deserialize(buf)?;
+        }
+        if version >= 8 {
+            account.dispatch_limits = AnchorDeserialize::deserialize(buf)?;
+            account.dispatch_window = if version >= 10 {
+                AnchorDeserialize::deserialize(buf)?
+            } else {
+                CalendarDispatchWindow::deserialize(buf)?.into()
+            };
+        }
+        if version >= 9 {
+            account.reserved_quota = AnchorDeserialize::deserialize(buf)?;
+            account.reservation_count = AnchorDeserialize::deserialize(buf)?;
+        }
+        if version >= 11 {
+            account.open_transfers = AnchorDeserialize::deserialize(buf)?;
+        }
+
+        Ok(account)
+    }
+}
+
+impl UsageRecord {
+    /// Whether the record was written with the current layout, judged from
+    /// its stored data as for `QuotaAccount::is_current_layout`
+    pub fn is_current_layout(account: &AccountInfo) -> bool {
+        Self::stored_layout_version(&account.data.borrow()) == Self::LAYOUT_VERSION
+    }
+
+    /// Quota account address for records that predate the `quota_account` field
+    fn derive_quota_account(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"quota", self.concession_id.as_bytes(), self.holder.as_ref()],
+            &crate::ID,
+        )
+        .0
+    }
+
+    /// Address the record is expected to live at for its addressing scheme
+    pub fn expected_address(&self) -> Result<Pubkey> {
+        let address = if self.sequence == Self::UNSEQUENCED {
+            Pubkey::create_program_address(
+                &[
+                    b"usage",
+                    self.shipment_id.as_bytes(),
+                    self.holder.as_ref(),
+                    &[self.bump],
+                ],
+                &crate::ID,
+            )
+        } else {
+            Pubkey::create_program_address(
+                &[
+                    b"usage",
+                    self.quota_account.as_ref(),
+                    &self.sequence.to_le_bytes(),
+                    &[self.bump],
+                ],
+                &crate::ID,
+            )
+        };
+        address.map_err(|_| error!(QuotaError::UnrecognizedAccountLayout))
+    }
+
+    /// Layout version of stored data, 0 for the layouts predating the field
+    pub fn stored_layout_version(data: &[u8]) -> u8 {
+        match data.len() {
+            LegacyUsageRecord::LEN | UNVERSIONED_USAGE_RECORD_LEN => 0,
+            _ => data.get(8).copied().unwrap_or(0),
+        }
+    }
+
+    /// Decode a usage record stored in any known layout into the current one
+    pub fn decode_stored(data: &[u8]) -> Result<Self> {
+        require!(
+            data.starts_with(Self::DISCRIMINATOR),
+            QuotaError::UnrecognizedAccountLayout
+        );
+        let mut body = &data[8..];
+
+        match data.len() {
+            LegacyUsageRecord::LEN => {
+                let mut record = LegacyUsageRecord::deserialize(&mut body)?.into_current();
+                record.quota_account = record.derive_quota_account();
+                Ok(record)
+            }
+            UNVERSIONED_USAGE_RECORD_LEN => Self::deserialize_fields(0, &mut body),
+            _ => {
+                let version = u8::deserialize(&mut body)?;
+                require!(
+                    (1..=Self::LAYOUT_VERSION).contains(&version),
+                    QuotaError::UnrecognizedAccountLayout
+                );
+                Self::deserialize_fields(version, &mut body)
+            }
+        }
+    }
+
+    /// Read the fields following `layout_version`, defaulting those newer
+    /// than `version`
+    fn deserialize_fields(version: u8, buf: &mut &[u8]) -> Result<Self> {
+        let mut record = Self {
+            layout_version: Self::LAYOUT_VERSION,
+            concession_id: AnchorDeserialize::deserialize(buf)?,
+            shipment_id: AnchorDeserialize::deserialize(buf)?,
+            amount: AnchorDeserialize::deserialize(buf)?,
+            timestamp: AnchorDeserialize::deserialize(buf)?,
+            holder: AnchorDeserialize::deserialize(buf)?,
+            quality_params: AnchorDeserialize::deserialize(buf)?,
+            source_location: AnchorDeserialize::deserialize(buf)?,
+            destination_location: AnchorDeserialize::deserialize(buf)?,
+            transport_details: AnchorDeserialize::deserialize(buf)?,
+            bump: AnchorDeserialize::deserialize(buf)?,
+            flagged_by: None,
+            flag_reason: String::new(),
+            flagged_at: 0,
+            quota_account: Pubkey::default(),
+            sequence: Self::UNSEQUENCED,
+            status: ShipmentStatus::Dispatched,
+            in_transit_at: 0,
+            delivered_at: 0,
+            cancelled_at: 0,
+            rejected_at: 0,
+            payer: Pubkey::default(),
+            assay_lab: None,
+            assay_sample_id: String::new(),
+            assayed_params: None,
+            assayed_at: 0,
+            delegate: None,
+            grade_debited: false,
+        };
+
+        if version >= 2 {
+            record.flagged_by = AnchorDeserialize::deserialize(buf)?;
+            record.flag_reason = AnchorDeserialize::deserialize(buf)?;
+            record.flagged_at = AnchorDeserialize::deserialize(buf)?;
+        }
+        if version >= 3 {
+            record.quota_account = AnchorDeserialize::deserialize(buf)?;
+            record.sequence = AnchorDeserialize::deserialize(buf)?;
+        } else {
+            record.quota_account = record.derive_quota_account();
+        }
+        if version >= 4 {
+            record.status = AnchorDeserialize::deserialize(buf)?;
+            record.in_transit_at = AnchorDeserialize::deserialize(buf)?;
+            record.delivered_at = AnchorDeserialize::deserialize(buf)?;
+            record.cancelled_at = AnchorDeserialize::deserialize(buf)?;
+            record.rejected_at = AnchorDeserialize::deserialize(buf)?;
+        }
+        if version >= 5 {
+            record.payer = AnchorDeserialize::deserialize(buf)?;
+        } else {
+            record.payer = record.holder;
+        }
+        if version >= 6 {
+            record.assay_lab = AnchorDeserialize::deserialize(buf)?;
+            record.assay_sample_id = AnchorDeserialize::deserialize(buf)?;
+            record.assayed_params = AnchorDeserialize::deserialize(buf)?;
+            record.assayed_at = AnchorDeserialize::deserialize(buf)?;
+        }
+        if version >= 7 {
+            record.delegate = AnchorDeserialize::deserialize(buf)?;
+        }
+        // Whether earlier shipments were charged to a grade is not known, so
+        // cancelling one leaves the grade sub-allocations untouched
+        if version >= 8 {
+            record.grade_debited = AnchorDeserialize::deserialize(buf)?;
+        }
+
+        Ok(record)
+    }
+}
+
+/// Dispatch counters of layouts v8 and v9, reset at each UTC day and
+/// Monday-based week
+#[derive(AnchorDeserialize)]
+struct CalendarDispatchWindow {
+    current_day: i64,
+    tonnage_today: u64,
+    shipments_today: u32,
+    /// Always the week of `current_day`, as both were reset together
+    _current_week: i64,
+    tonnage_this_week: u64,
+}
+
+impl From<CalendarDispatchWindow> for DispatchWindow {
+    /// The calendar counts do not say when each dispatch was made, so they
+    /// are all placed at the latest moment they could have been: today's in
+    /// the last hour of the counted day and the week's on that day
+    fn from(calendar: CalendarDispatchWindow) -> Self {
+        let hours_per_day = DispatchWindow::SECONDS_PER_DAY / DispatchWindow::SECONDS_PER_HOUR;
+        let last_hour = (calendar.current_day + 1) * hours_per_day - 1;
+        let hour = last_hour.rem_euclid(DispatchWindow::HOURLY_BUCKETS as i64) as usize;
+        let day = calendar.current_day.rem_euclid(DispatchWindow::DAILY_BUCKETS as i64) as usize;
+
+        let mut window = DispatchWindow {
+            latest_hour: last_hour,
+            latest_day: calendar.current_day,
+            ..DispatchWindow::default()
+        };
+        window.hourly_tonnage[hour] = calendar.tonnage_today;
+        window.hourly_shipments[hour] = calendar.shipments_today;
+        window.daily_tonnage[day] = calendar.tonnage_this_week;
+        window
+    }
+}
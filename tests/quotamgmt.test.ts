@@ -1,7 +1,14 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { expect } from "chai";
-import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { createHash } from "crypto";
+import {
+  Ed25519Program,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
 
 // Import the correct type for the program from the generated types.
 // This is essential for proper type-checking.
@@ -45,6 +52,8 @@ describe("quota-management", () => {
   }
 
   // Test accounts
+  // The config admin must be the program's upgrade authority, the provider wallet on localnet
+  const admin = (provider.wallet as anchor.Wallet).payer;
   const regulator = Keypair.generate();
   const holder1 = Keypair.generate();
   const holder2 = Keypair.generate();
@@ -56,20 +65,54 @@ describe("quota-management", () => {
   const validityPeriod = new anchor.BN(Date.now() / 1000 + 365 * 24 * 60 * 60); // 1 year from now
 
   // PDAs
+  let programConfig: PublicKey;
+  let programData: PublicKey;
+  let regulatorRecord: PublicKey;
   let quotaAccount1: PublicKey;
   let quotaAccount2: PublicKey;
+  let clearance1: PublicKey;
+  let qualityPolicy: PublicKey;
   let quotaBump1: number;
   let quotaBump2: number;
 
+  // Usage and transfer records are addressed by per-quota sequence numbers
+  const sequencedPda = (prefix: string, quota: PublicKey, sequence: anchor.BN) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from(prefix), quota.toBuffer(), sequence.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+
+  const nextUsageRecord = async (quota: PublicKey) =>
+    sequencedPda("usage", quota, (await program.account.quotaAccount.fetch(quota)).usageCount);
+
+  const nextTransferRecord = async (quota: PublicKey) =>
+    sequencedPda("transfer", quota, (await program.account.quotaAccount.fetch(quota)).transferCount);
+
   before(async () => {
     console.log("Program ID:", program.programId.toString());
 
     // Airdrop SOL to test accounts
+    await provider.connection.requestAirdrop(admin.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL);
     await provider.connection.requestAirdrop(regulator.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL);
     await provider.connection.requestAirdrop(holder1.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL);
     await provider.connection.requestAirdrop(holder2.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL);
 
     // Find PDAs
+    [programConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      program.programId
+    );
+
+    [programData] = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+
+    [regulatorRecord] = PublicKey.findProgramAddressSync(
+      [Buffer.from("regulator"), regulator.publicKey.toBuffer()],
+      program.programId
+    );
+
     [quotaAccount1, quotaBump1] = PublicKey.findProgramAddressSync(
       [Buffer.from("quota"), Buffer.from(concessionId1), holder1.publicKey.toBuffer()],
       program.programId
@@ -80,6 +123,16 @@ describe("quota-management", () => {
       program.programId
     );
 
+    [clearance1] = PublicKey.findProgramAddressSync(
+      [Buffer.from("clearance"), quotaAccount1.toBuffer()],
+      program.programId
+    );
+
+    [qualityPolicy] = PublicKey.findProgramAddressSync(
+      [Buffer.from("quality_policy"), regulator.publicKey.toBuffer(), Buffer.from("RAJMAHAL")],
+      program.programId
+    );
+
     console.log("Quota Account 1:", quotaAccount1.toString());
     console.log("Quota Account 2:", quotaAccount2.toString());
 
@@ -87,6 +140,117 @@ describe("quota-management", () => {
     await new Promise(resolve => setTimeout(resolve, 2000));
   });
 
+  describe("Program Config", () => {
+    it("Only lets the upgrade authority initialize the config", async () => {
+      const impostor = Keypair.generate();
+      await provider.connection.requestAirdrop(impostor.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+      await new Promise(resolve => setTimeout(resolve, 1000));
+
+      try {
+        await program.methods
+          .initializeConfig()
+          .accounts({
+            programConfig,
+            program: program.programId,
+            programData,
+            admin: impostor.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([impostor])
+          .rpc();
+        expect.fail("initialize_config should require the upgrade authority");
+      } catch (error: any) {
+        expect(error.toString()).to.include("UnauthorizedAdmin");
+      }
+    });
+
+    it("Initializes the config and registers a regulator", async () => {
+      await program.methods
+        .initializeConfig()
+        .accounts({
+          programConfig,
+          program: program.programId,
+          programData,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+
+      await program.methods
+        .addRegulator("State Directorate of Mines", "Jharkhand")
+        .accounts({
+          programConfig,
+          regulatorRecord,
+          regulator: regulator.publicKey,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+
+      const configData = await program.account.programConfig.fetch(programConfig);
+      expect(configData.admin.toString()).to.equal(admin.publicKey.toString());
+      expect(configData.regulatorCount).to.equal(1);
+
+      const regulatorData = await program.account.regulatorRecord.fetch(regulatorRecord);
+      expect(regulatorData.jurisdiction).to.equal("Jharkhand");
+      expect(regulatorData.status).to.deep.equal({ active: {} });
+    });
+
+    it("Rejects quota issuance by an unregistered signer", async () => {
+      const impostor = Keypair.generate();
+      await provider.connection.requestAirdrop(impostor.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+      await new Promise(resolve => setTimeout(resolve, 1000));
+
+      const [impostorRecord] = PublicKey.findProgramAddressSync(
+        [Buffer.from("regulator"), impostor.publicKey.toBuffer()],
+        program.programId
+      );
+      const [impostorQuota] = PublicKey.findProgramAddressSync(
+        [Buffer.from("quota"), Buffer.from("ROGUE001"), holder1.publicKey.toBuffer()],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .initializeQuota("ROGUE001", allocatedQuota, validityPeriod, { annual: {} }, "", "")
+          .accounts({
+            quotaAccount: impostorQuota,
+            holder: holder1.publicKey,
+            regulator: impostor.publicKey,
+            regulatorRecord: impostorRecord,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([impostor])
+          .rpc();
+        expect.fail("initialize_quota should require a registered regulator");
+      } catch (error: any) {
+        expect(error.toString()).to.include("AccountNotInitialized");
+      }
+    });
+
+    it("Disables and re-enables a regulator", async () => {
+      await program.methods
+        .disableRegulator()
+        .accounts({ programConfig, regulatorRecord, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+
+      let regulatorData = await program.account.regulatorRecord.fetch(regulatorRecord);
+      expect(regulatorData.status).to.deep.equal({ disabled: {} });
+
+      await program.methods
+        .enableRegulator()
+        .accounts({ programConfig, regulatorRecord, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+
+      regulatorData = await program.account.regulatorRecord.fetch(regulatorRecord);
+      expect(regulatorData.status).to.deep.equal({ active: {} });
+    });
+  });
+
   describe("Initialize Quota", () => {
     it("Successfully initializes a quota", async () => {
       try {
@@ -95,12 +259,15 @@ describe("quota-management", () => {
             concessionId1,
             allocatedQuota,
             validityPeriod,
-            { annual: {} } // QuotaType::Annual
+            { annual: {} }, // QuotaType::Annual
+            "Rajmahal Coalfield, Jharkhand",
+            "EC/J-11015/123/2019-IA.II(M)"
           )
           .accounts({
             quotaAccount: quotaAccount1,
             holder: holder1.publicKey,
             regulator: regulator.publicKey,
+            regulatorRecord,
             systemProgram: SystemProgram.programId,
           })
           .signers([regulator])
@@ -117,6 +284,7 @@ describe("quota-management", () => {
         expect(quotaData.allocatedQuota.toString()).to.equal(allocatedQuota.toString());
         expect(quotaData.usedQuota.toString()).to.equal("0");
         expect(quotaData.availableQuota.toString()).to.equal(allocatedQuota.toString());
+        expect(quotaData.miningRegion).to.equal("Rajmahal Coalfield, Jharkhand");
 
         console.log("Quota initialized successfully!");
       } catch (error) {
@@ -126,6 +294,127 @@ describe("quota-management", () => {
     });
   });
 
+  // Regional limits, shared with the grade checks further down
+  const policyBounds = {
+    minGcv: 2000,
+    maxGcv: 8000,
+    maxMoisture: 5000, // 50.00%
+    maxAsh: 5000,
+    maxSulphur: 1000, // 10.00%
+    minVolatileMatter: null,
+    maxVolatileMatter: null,
+    minFixedCarbon: null,
+    maxFixedCarbon: null,
+    gradeBands: [] as { grade: object; minGcv: number; maxGcv: number }[],
+    proximateTolerance: null as number | null,
+  };
+
+  describe("Quality Policy", () => {
+    it("Creates a regional policy and assigns it to the quota", async () => {
+      await program.methods
+        .createQualityPolicy("RAJMAHAL", "Rajmahal Coalfield, Jharkhand", policyBounds)
+        .accounts({
+          qualityPolicy,
+          regulatorRecord,
+          regulator: regulator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([regulator])
+        .rpc();
+
+      await program.methods
+        .assignQualityPolicy()
+        .accounts({
+          quotaAccount: quotaAccount1,
+          qualityPolicy,
+          regulator: regulator.publicKey,
+        })
+        .signers([regulator])
+        .rpc();
+
+      const quotaData = await program.account.quotaAccount.fetch(quotaAccount1);
+      expect(quotaData.qualityPolicy.toString()).to.equal(qualityPolicy.toString());
+    });
+
+    it("Rejects bounds with an inverted range", async () => {
+      try {
+        await program.methods
+          .updateQualityPolicy({ ...policyBounds, minGcv: 9000 })
+          .accounts({
+            qualityPolicy,
+            regulator: regulator.publicKey,
+          })
+          .signers([regulator])
+          .rpc();
+        expect.fail("update_quality_policy should reject min_gcv above max_gcv");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidQualityParameters");
+      }
+    });
+  });
+
+  describe("Environmental Clearance", () => {
+    it("Registers a clearance that every dispatch is charged against", async () => {
+      const now = Math.floor(Date.now() / 1000);
+
+      await program.methods
+        .registerClearance(
+          "MoEFCC",
+          "EC/J-11015/123/2024-IA.II(M)",
+          Array(32).fill(7),
+          new anchor.BN(6000),
+          new anchor.BN(now - 60),
+          validityPeriod
+        )
+        .accounts({
+          quotaAccount: quotaAccount1,
+          clearance: clearance1,
+          regulator: regulator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([regulator])
+        .rpc();
+
+      const clearanceData = await program.account.environmentalClearance.fetch(clearance1);
+      expect(clearanceData.annualCapacity.toString()).to.equal("6000");
+      expect(clearanceData.status).to.deep.equal({ active: {} });
+
+      const quotaData = await program.account.quotaAccount.fetch(quotaAccount1);
+      expect(quotaData.environmentalClearance).to.equal("EC/J-11015/123/2024-IA.II(M)");
+    });
+
+    it("Rejects a dispatch beyond the clearance's annual capacity", async () => {
+      try {
+        await program.methods
+          .useQuota(new anchor.BN(7000), "SHIP-OVERCAP", {
+            grossCalorificValue: 5500,
+            moistureContent: 1200,
+            ashContent: 1500,
+            sulphurContent: 50,
+            volatileMatter: 3500,
+            fixedCarbon: 5000,
+            coalGrade: { gradeB: {} },
+            sizeClassification: "0-50mm",
+          })
+          .accounts({
+            quotaAccount: quotaAccount1,
+            usageRecord: await nextUsageRecord(quotaAccount1),
+            roleAssignment: null,
+            delegate: null,
+            clearance: clearance1,
+            qualityPolicy,
+            authority: holder1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([holder1])
+          .rpc();
+        expect.fail("use_quota should respect the clearance's annual capacity");
+      } catch (error: any) {
+        expect(error.toString()).to.include("ClearanceCapacityExceeded");
+      }
+    });
+  });
+
   describe("Use Quota", () => {
     const shipmentId = "SHIP001";
     const usageAmount = new anchor.BN(1000); // 1,000 tons
@@ -133,10 +422,7 @@ describe("quota-management", () => {
 
     before(async () => {
       // Find usage record PDA
-      [usageRecord] = PublicKey.findProgramAddressSync(
-        [Buffer.from("usage"), Buffer.from(shipmentId), holder1.publicKey.toBuffer()],
-        program.programId
-      );
+      usageRecord = await nextUsageRecord(quotaAccount1);
       console.log("Usage Record:", usageRecord.toString());
     });
 
@@ -158,7 +444,11 @@ describe("quota-management", () => {
           .accounts({
             quotaAccount: quotaAccount1,
             usageRecord: usageRecord,
-            holder: holder1.publicKey,
+            roleAssignment: null,
+            delegate: null,
+            clearance: clearance1,
+            qualityPolicy,
+            authority: holder1.publicKey,
             systemProgram: SystemProgram.programId,
           })
           .signers([holder1])
@@ -176,6 +466,8 @@ describe("quota-management", () => {
         expect(usageData.concessionId).to.equal(concessionId1);
         expect(usageData.shipmentId).to.equal(shipmentId);
         expect(usageData.amount.toString()).to.equal(usageAmount.toString());
+        expect(usageData.sequence.toString()).to.equal("0");
+        expect(usageData.quotaAccount.toString()).to.equal(quotaAccount1.toString());
 
         console.log("Quota used successfully!");
       } catch (error) {
@@ -184,4 +476,1966 @@ describe("quota-management", () => {
       }
     });
   });
+
+  describe("Grade and Proximate Checks", () => {
+    const setPolicy = (bounds: typeof policyBounds) =>
+      program.methods
+        .updateQualityPolicy(bounds)
+        .accounts({
+          qualityPolicy,
+          regulator: regulator.publicKey,
+        })
+        .signers([regulator])
+        .rpc();
+
+    const dispatch = (coalGrade: object, moistureContent: number) =>
+      nextUsageRecord(quotaAccount1).then((usageRecord) =>
+        program.methods
+          .useQuota(new anchor.BN(10), "SHIP-GRADE", {
+            grossCalorificValue: 6500,
+            moistureContent,
+            ashContent: 1500,
+            sulphurContent: 50,
+            volatileMatter: 3000,
+            fixedCarbon: 4500,
+            coalGrade,
+            sizeClassification: "0-50mm",
+          })
+          .accounts({
+            quotaAccount: quotaAccount1,
+            usageRecord,
+            roleAssignment: null,
+            delegate: null,
+            clearance: clearance1,
+            qualityPolicy,
+            authority: holder1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([holder1])
+          .rpc()
+      );
+
+    before(async () => {
+      await setPolicy({
+        ...policyBounds,
+        gradeBands: [
+          { grade: { gradeA: {} }, minGcv: 6100, maxGcv: 8000 },
+          { grade: { gradeB: {} }, minGcv: 5600, maxGcv: 6100 },
+        ],
+        proximateTolerance: 200, // 2.00%
+      });
+    });
+
+    after(async () => {
+      await setPolicy(policyBounds);
+    });
+
+    it("Rejects a grade that contradicts the GCV band", async () => {
+      try {
+        await dispatch({ gradeB: {} }, 1000);
+        expect.fail("a 6500 kcal/kg shipment should not be declared grade B");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidCoalGrade");
+      }
+    });
+
+    it("Rejects a proximate analysis that does not add up to 100%", async () => {
+      try {
+        await dispatch({ gradeA: {} }, 2000);
+        expect.fail("components summing to 110% should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidQualityParameters");
+      }
+    });
+
+    it("Allows no more grade bands than there are coal grades", async () => {
+      const band = { grade: { gradeE: {} }, minGcv: 2200, maxGcv: 3100 };
+
+      try {
+        await setPolicy({ ...policyBounds, gradeBands: Array(9).fill(band) });
+        expect.fail("a ninth grade band should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidQualityParameters");
+      }
+    });
+  });
+
+  describe("Shipment Details", () => {
+    it("Lets the holder fill in shipment details after dispatch", async () => {
+      const usageRecord = sequencedPda("usage", quotaAccount1, new anchor.BN(0));
+
+      await program.methods
+        .updateShipmentDetails("Rajmahal OCP Siding 2", "NTPC Farakka", "WB-23-4411 / Sharma Roadways")
+        .accounts({
+          quotaAccount: quotaAccount1,
+          usageRecord,
+          delegate: null,
+          authority: holder1.publicKey,
+        })
+        .signers([holder1])
+        .rpc();
+
+      const usageData = await program.account.usageRecord.fetch(usageRecord);
+      expect(usageData.sourceLocation).to.equal("Rajmahal OCP Siding 2");
+      expect(usageData.destinationLocation).to.equal("NTPC Farakka");
+    });
+
+    it("Rejects locations longer than the record allows", async () => {
+      const usageRecord = sequencedPda("usage", quotaAccount1, new anchor.BN(0));
+
+      try {
+        await program.methods
+          .updateShipmentDetails(null, "X".repeat(101), null)
+          .accounts({
+            quotaAccount: quotaAccount1,
+            usageRecord,
+            delegate: null,
+            authority: holder1.publicKey,
+          })
+          .signers([holder1])
+          .rpc();
+        expect.fail("update_shipment_details should enforce MAX_LOCATION_LEN");
+      } catch (error: any) {
+        expect(error.toString()).to.include("LocationTooLong");
+      }
+    });
+  });
+
+  describe("Shipment Lifecycle", () => {
+    it("Moves a shipment from dispatched through in transit to delivered", async () => {
+      const usageRecord = sequencedPda("usage", quotaAccount1, new anchor.BN(0));
+
+      let usageData = await program.account.usageRecord.fetch(usageRecord);
+      expect(usageData.status).to.deep.equal({ dispatched: {} });
+
+      await program.methods
+        .markShipmentInTransit()
+        .accounts({
+          quotaAccount: quotaAccount1,
+          usageRecord,
+          roleAssignment: null,
+          delegate: null,
+          authority: holder1.publicKey,
+        })
+        .signers([holder1])
+        .rpc();
+
+      await program.methods
+        .confirmDelivery()
+        .accounts({
+          quotaAccount: quotaAccount1,
+          usageRecord,
+          roleAssignment: null,
+          authority: holder1.publicKey,
+        })
+        .signers([holder1])
+        .rpc();
+
+      usageData = await program.account.usageRecord.fetch(usageRecord);
+      expect(usageData.status).to.deep.equal({ delivered: {} });
+      expect(usageData.inTransitAt.toNumber()).to.be.greaterThan(0);
+      expect(usageData.deliveredAt.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("Freezes shipment details once delivered", async () => {
+      const usageRecord = sequencedPda("usage", quotaAccount1, new anchor.BN(0));
+
+      try {
+        await program.methods
+          .updateShipmentDetails(null, "NTPC Kahalgaon", null)
+          .accounts({
+            quotaAccount: quotaAccount1,
+            usageRecord,
+            delegate: null,
+            authority: holder1.publicKey,
+          })
+          .signers([holder1])
+          .rpc();
+        expect.fail("delivered shipments should be immutable");
+      } catch (error: any) {
+        expect(error.toString()).to.include("ShipmentAlreadyFinalised");
+      }
+    });
+
+    it("Refuses to move a delivered shipment backwards", async () => {
+      const usageRecord = sequencedPda("usage", quotaAccount1, new anchor.BN(0));
+
+      try {
+        await program.methods
+          .cancelShipment()
+          .accounts({
+            programConfig,
+            quotaAccount: quotaAccount1,
+            usageRecord,
+            clearance: clearance1,
+            roleAssignment: null,
+            delegate: null,
+            dispatchDelegate: null,
+            authority: holder1.publicKey,
+          })
+          .signers([holder1])
+          .rpc();
+        expect.fail("cancel_shipment should reject a delivered shipment");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidShipmentTransition");
+      }
+    });
+
+    it("Refunds the tonnage when a dispatched shipment is cancelled", async () => {
+      const usageRecord = await nextUsageRecord(quotaAccount1);
+      const amount = new anchor.BN(250);
+
+      await program.methods
+        .useQuota(amount, "SHIP-CANCEL", {
+          grossCalorificValue: 5200,
+          moistureContent: 1000,
+          ashContent: 1800,
+          sulphurContent: 60,
+          volatileMatter: 3000,
+          fixedCarbon: 5200,
+          coalGrade: { gradeC: {} },
+          sizeClassification: "0-50mm",
+        })
+        .accounts({
+          quotaAccount: quotaAccount1,
+          usageRecord,
+          roleAssignment: null,
+          delegate: null,
+          clearance: clearance1,
+          qualityPolicy,
+          authority: holder1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([holder1])
+        .rpc();
+
+      const before = await program.account.quotaAccount.fetch(quotaAccount1);
+      const clearanceBefore = await program.account.environmentalClearance.fetch(clearance1);
+
+      await program.methods
+        .cancelShipment()
+        .accounts({
+          programConfig,
+          quotaAccount: quotaAccount1,
+          usageRecord,
+          clearance: clearance1,
+          roleAssignment: null,
+          delegate: null,
+          dispatchDelegate: null,
+          authority: holder1.publicKey,
+        })
+        .signers([holder1])
+        .rpc();
+
+      const after = await program.account.quotaAccount.fetch(quotaAccount1);
+      expect(after.availableQuota.toString()).to.equal(before.availableQuota.add(amount).toString());
+      expect(after.usedQuota.toString()).to.equal(before.usedQuota.sub(amount).toString());
+
+      const clearanceAfter = await program.account.environmentalClearance.fetch(clearance1);
+      expect(clearanceAfter.usedThisYear.toString()).to.equal(
+        clearanceBefore.usedThisYear.sub(amount).toString()
+      );
+
+      const usageData = await program.account.usageRecord.fetch(usageRecord);
+      expect(usageData.status).to.deep.equal({ cancelled: {} });
+    });
+  });
+
+  describe("Transfer Quota", () => {
+    const transferAmount = new anchor.BN(500);
+
+    before(async () => {
+      await program.methods
+        .initializeQuota(concessionId2, allocatedQuota, validityPeriod, { annual: {} }, "Talcher Coalfield, Odisha", "")
+        .accounts({
+          quotaAccount: quotaAccount2,
+          holder: holder2.publicKey,
+          regulator: regulator.publicKey,
+          regulatorRecord,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([regulator])
+        .rpc();
+    });
+
+    it("Moves tonnage only after regulator approval and recipient acceptance", async () => {
+      const before1 = await program.account.quotaAccount.fetch(quotaAccount1);
+      const before2 = await program.account.quotaAccount.fetch(quotaAccount2);
+
+      const transferRecord = await nextTransferRecord(quotaAccount1);
+
+      await program.methods
+        .requestTransfer(transferAmount, "Commercial sale to MINE002", { commercial: {} })
+        .accounts({
+          fromQuota: quotaAccount1,
+          toQuota: quotaAccount2,
+          transferRecord,
+          holder: holder1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([holder1])
+        .rpc();
+
+      // Nothing moves on request
+      let after1 = await program.account.quotaAccount.fetch(quotaAccount1);
+      expect(after1.availableQuota.toString()).to.equal(before1.availableQuota.toString());
+
+      await program.methods
+        .approveTransfer()
+        .accounts({ fromQuota: quotaAccount1, transferRecord, regulator: regulator.publicKey })
+        .signers([regulator])
+        .rpc();
+
+      await program.methods
+        .acceptTransfer()
+        .accounts({ toQuota: quotaAccount2, transferRecord, holder: holder2.publicKey })
+        .signers([holder2])
+        .rpc();
+
+      await program.methods
+        .executeTransfer()
+        .accounts({
+          fromQuota: quotaAccount1,
+          toQuota: quotaAccount2,
+          transferRecord,
+          executor: holder1.publicKey,
+        })
+        .signers([holder1])
+        .rpc();
+
+      after1 = await program.account.quotaAccount.fetch(quotaAccount1);
+      const after2 = await program.account.quotaAccount.fetch(quotaAccount2);
+      expect(after1.availableQuota.toString()).to.equal(
+        before1.availableQuota.sub(transferAmount).toString()
+      );
+      expect(after2.availableQuota.toString()).to.equal(
+        before2.availableQuota.add(transferAmount).toString()
+      );
+
+      const record = await program.account.transferRecord.fetch(transferRecord);
+      expect(record.status).to.deep.equal({ executed: {} });
+      expect(record.transferType).to.deep.equal({ commercial: {} });
+      expect(record.authorizedBy.toString()).to.equal(regulator.publicKey.toString());
+    });
+
+    it("Dispatches from a quota without a clearance or quality policy under the default bounds", async () => {
+      const [clearance2] = PublicKey.findProgramAddressSync(
+        [Buffer.from("clearance"), quotaAccount2.toBuffer()],
+        program.programId
+      );
+      const qualityParams = (grossCalorificValue: number) => ({
+        grossCalorificValue,
+        moistureContent: 1000,
+        ashContent: 1800,
+        sulphurContent: 60,
+        volatileMatter: 3000,
+        fixedCarbon: 5200,
+        coalGrade: { gradeC: {} },
+        sizeClassification: "0-50mm",
+      });
+      const useQuota = (usageRecord: PublicKey, grossCalorificValue: number) =>
+        program.methods
+          .useQuota(new anchor.BN(100), "SHIP-NOPOLICY", qualityParams(grossCalorificValue))
+          .accounts({
+            quotaAccount: quotaAccount2,
+            usageRecord,
+            roleAssignment: null,
+            delegate: null,
+            clearance: clearance2,
+            qualityPolicy: null,
+            authority: holder2.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([holder2]);
+
+      const usageRecord = await nextUsageRecord(quotaAccount2);
+      try {
+        await useQuota(usageRecord, 9000).rpc();
+        expect.fail("the default bounds should cap GCV at 8000");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidGCVValue");
+      }
+
+      await useQuota(usageRecord, 5500).rpc();
+      expect((await program.account.usageRecord.fetch(usageRecord)).amount.toString()).to.equal("100");
+
+      // Cancelled again so the quota can still be closed once revoked
+      await program.methods
+        .cancelShipment()
+        .accounts({
+          programConfig,
+          quotaAccount: quotaAccount2,
+          usageRecord,
+          clearance: clearance2,
+          roleAssignment: null,
+          delegate: null,
+          dispatchDelegate: null,
+          authority: holder2.publicKey,
+        })
+        .signers([holder2])
+        .rpc();
+    });
+
+    it("Does not refund a backdated clearance for a shipment it never charged", async () => {
+      const [clearance2] = PublicKey.findProgramAddressSync(
+        [Buffer.from("clearance"), quotaAccount2.toBuffer()],
+        program.programId
+      );
+      const usageRecord = await nextUsageRecord(quotaAccount2);
+      await program.methods
+        .useQuota(new anchor.BN(100), "SHIP-PRE-EC", {
+          grossCalorificValue: 5500,
+          moistureContent: 1000,
+          ashContent: 1800,
+          sulphurContent: 60,
+          volatileMatter: 3000,
+          fixedCarbon: 5200,
+          coalGrade: { gradeC: {} },
+          sizeClassification: "0-50mm",
+        })
+        .accounts({
+          quotaAccount: quotaAccount2,
+          usageRecord,
+          roleAssignment: null,
+          delegate: null,
+          clearance: clearance2,
+          qualityPolicy: null,
+          authority: holder2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([holder2])
+        .rpc();
+
+      // Registered after the dispatch, but valid from an hour before it
+      await program.methods
+        .registerClearance(
+          "SEIAA Odisha",
+          "EC/O-22011/045/2024",
+          Array(32).fill(9),
+          new anchor.BN(100000),
+          new anchor.BN(Math.floor(Date.now() / 1000) - 60 * 60),
+          validityPeriod
+        )
+        .accounts({
+          quotaAccount: quotaAccount2,
+          clearance: clearance2,
+          regulator: regulator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([regulator])
+        .rpc();
+
+      await program.methods
+        .cancelShipment()
+        .accounts({
+          programConfig,
+          quotaAccount: quotaAccount2,
+          usageRecord,
+          clearance: clearance2,
+          roleAssignment: null,
+          delegate: null,
+          dispatchDelegate: null,
+          authority: holder2.publicKey,
+        })
+        .signers([holder2])
+        .rpc();
+
+      const clearanceData = await program.account.environmentalClearance.fetch(clearance2);
+      expect(clearanceData.usedThisYear.toString()).to.equal("0");
+    });
+  });
+
+  describe("Update Quota", () => {
+    it("Lets the regulator resize a quota", async () => {
+      const newAllocation = new anchor.BN(12000);
+      await program.methods
+        .updateQuota(newAllocation, null, null, "Annual review")
+        .accounts({
+          quotaAccount: quotaAccount2,
+          regulator: regulator.publicKey,
+        })
+        .signers([regulator])
+        .rpc();
+
+      const quotaData = await program.account.quotaAccount.fetch(quotaAccount2);
+      expect(quotaData.allocatedQuota.toString()).to.equal(newAllocation.toString());
+      expect(quotaData.availableQuota.toString()).to.equal(
+        newAllocation.sub(quotaData.usedQuota).toString()
+      );
+    });
+
+    it("Refunds a cancelled shipment only up to a cut allocation", async () => {
+      const [clearance2] = PublicKey.findProgramAddressSync(
+        [Buffer.from("clearance"), quotaAccount2.toBuffer()],
+        program.programId
+      );
+      const usageRecord = await nextUsageRecord(quotaAccount2);
+      await program.methods
+        .useQuota(new anchor.BN(100), "SHIP-BEFORE-CUT", {
+          grossCalorificValue: 5500,
+          moistureContent: 1000,
+          ashContent: 1800,
+          sulphurContent: 60,
+          volatileMatter: 3000,
+          fixedCarbon: 5200,
+          coalGrade: { gradeC: {} },
+          sizeClassification: "0-50mm",
+        })
+        .accounts({
+          quotaAccount: quotaAccount2,
+          usageRecord,
+          roleAssignment: null,
+          delegate: null,
+          clearance: clearance2,
+          qualityPolicy: null,
+          authority: holder2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([holder2])
+        .rpc();
+
+      // Cut to 50 t below what is now used
+      const used = (await program.account.quotaAccount.fetch(quotaAccount2)).usedQuota;
+      const updateQuota = (allocation: anchor.BN, reason: string) =>
+        program.methods
+          .updateQuota(allocation, null, null, reason)
+          .accounts({
+            quotaAccount: quotaAccount2,
+            regulator: regulator.publicKey,
+          })
+          .signers([regulator])
+          .rpc();
+      await updateQuota(used.sub(new anchor.BN(50)), "Mid-year cut");
+
+      await program.methods
+        .cancelShipment()
+        .accounts({
+          programConfig,
+          quotaAccount: quotaAccount2,
+          usageRecord,
+          clearance: clearance2,
+          roleAssignment: null,
+          delegate: null,
+          dispatchDelegate: null,
+          authority: holder2.publicKey,
+        })
+        .signers([holder2])
+        .rpc();
+
+      const quotaData = await program.account.quotaAccount.fetch(quotaAccount2);
+      expect(quotaData.availableQuota.toString()).to.equal("50");
+      expect(quotaData.status).to.deep.equal({ active: {} });
+
+      await updateQuota(new anchor.BN(12000), "Cut reversed");
+    });
+
+    it("Rejects updates from anyone but the regulator", async () => {
+      try {
+        await program.methods
+          .updateQuota(new anchor.BN(1), null, null, "Not allowed")
+          .accounts({
+            quotaAccount: quotaAccount2,
+            regulator: holder2.publicKey,
+          })
+          .signers([holder2])
+          .rpc();
+        expect.fail("update_quota should reject a non-regulator signer");
+      } catch (error: any) {
+        expect(error.toString()).to.include("MissingPermission");
+      }
+    });
+  });
+
+  describe("Update Quota Metadata", () => {
+    it("Lets the regulator record a renewed environmental clearance", async () => {
+      await program.methods
+        .updateQuotaMetadata(null, "EC/J-11015/123/2024-IA.II(M)")
+        .accounts({
+          quotaAccount: quotaAccount1,
+          regulator: regulator.publicKey,
+        })
+        .signers([regulator])
+        .rpc();
+
+      const quotaData = await program.account.quotaAccount.fetch(quotaAccount1);
+      expect(quotaData.environmentalClearance).to.equal("EC/J-11015/123/2024-IA.II(M)");
+      expect(quotaData.miningRegion).to.equal("Rajmahal Coalfield, Jharkhand");
+    });
+
+    it("Does not let the holder change quota metadata", async () => {
+      try {
+        await program.methods
+          .updateQuotaMetadata("Anywhere", null)
+          .accounts({
+            quotaAccount: quotaAccount1,
+            regulator: holder1.publicKey,
+          })
+          .signers([holder1])
+          .rpc();
+        expect.fail("update_quota_metadata should be regulator-only");
+      } catch (error: any) {
+        expect(error.toString()).to.include("MissingPermission");
+      }
+    });
+  });
+
+  describe("Suspend and Reactivate Quota", () => {
+    it("Suspends an active quota", async () => {
+      await program.methods
+        .suspendQuota("Environmental inspection pending")
+        .accounts({
+          quotaAccount: quotaAccount2,
+          regulator: regulator.publicKey,
+        })
+        .signers([regulator])
+        .rpc();
+
+      const quotaData = await program.account.quotaAccount.fetch(quotaAccount2);
+      expect(quotaData.status).to.deep.equal({ suspended: {} });
+    });
+
+    it("Reactivates a suspended quota", async () => {
+      await program.methods
+        .reactivateQuota()
+        .accounts({
+          quotaAccount: quotaAccount2,
+          regulator: regulator.publicKey,
+        })
+        .signers([regulator])
+        .rpc();
+
+      const quotaData = await program.account.quotaAccount.fetch(quotaAccount2);
+      expect(quotaData.status).to.deep.equal({ active: {} });
+    });
+  });
+
+  describe("Close Accounts", () => {
+    it("Keeps delivered usage records for the retention period", async () => {
+      const usageRecord = sequencedPda("usage", quotaAccount1, new anchor.BN(0));
+
+      try {
+        await program.methods
+          .closeUsageRecord()
+          .accounts({
+            quotaAccount: quotaAccount1,
+            usageRecord,
+            payer: holder1.publicKey,
+            holder: holder1.publicKey,
+          })
+          .signers([holder1])
+          .rpc();
+        expect.fail("close_usage_record should wait out the retention period");
+      } catch (error: any) {
+        expect(error.toString()).to.include("RecordNotClosable");
+      }
+    });
+
+    it("Refuses to close a quota that is still active", async () => {
+      try {
+        await program.methods
+          .closeQuotaAccount()
+          .accounts({
+            quotaAccount: quotaAccount1,
+            regulator: regulator.publicKey,
+          })
+          .signers([regulator])
+          .rpc();
+        expect.fail("close_quota_account should require an expired or revoked quota");
+      } catch (error: any) {
+        expect(error.toString()).to.include("QuotaNotClosable");
+      }
+    });
+  });
+
+  describe("Expire Quota", () => {
+    it("Refuses to expire a quota before its validity period ends", async () => {
+      try {
+        await program.methods
+          .expireQuota()
+          .accounts({
+            programConfig,
+            quotaAccount: quotaAccount1,
+            cranker: holder2.publicKey,
+          })
+          .signers([holder2])
+          .rpc();
+        expect.fail("expire_quota should wait for validity_period to pass");
+      } catch (error: any) {
+        expect(error.toString()).to.include("ValidityPeriodNotElapsed");
+      }
+    });
+  });
+
+  describe("Migrate Accounts", () => {
+    it("Refuses to migrate an account already in the current layout", async () => {
+      try {
+        await program.methods
+          .migrateQuotaAccount()
+          .accounts({
+            quotaAccount: quotaAccount1,
+            regulator: regulator.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([regulator])
+          .rpc();
+        expect.fail("migrate_quota_account should reject a current-layout account");
+      } catch (error: any) {
+        expect(error.toString()).to.include("AccountAlreadyMigrated");
+      }
+    });
+
+    it("Rejects instructions on an account still in an older layout", async () => {
+      // Loaded into the validator from tests/fixtures in layout v8
+      const legacyQuota = new PublicKey("H4sq4YRHRCQ6tXJAgT4w6Vo8Qnh8Fpk5myy4HfQwPWmE");
+
+      try {
+        await program.methods
+          .suspendQuota("Pending migration")
+          .accounts({
+            quotaAccount: legacyQuota,
+            regulator: regulator.publicKey,
+          })
+          .signers([regulator])
+          .rpc();
+        expect.fail("suspend_quota should reject an un-migrated account");
+      } catch (error: any) {
+        expect(error.toString()).to.include("AccountMigrationRequired");
+      }
+    });
+
+    it("Stamps new accounts with the current layout version", async () => {
+      const quotaData = await program.account.quotaAccount.fetch(quotaAccount1);
+      expect(quotaData.layoutVersion).to.equal(11);
+    });
+  });
+
+  describe("Roles", () => {
+    const clerk = Keypair.generate();
+    const inspector = Keypair.generate();
+    const shipmentId = "SHIP002";
+    let clerkRole: PublicKey;
+    let inspectorRole: PublicKey;
+    let usageRecord: PublicKey;
+
+    const qualityParams = {
+      grossCalorificValue: 5200,
+      moistureContent: 1100,
+      ashContent: 1800,
+      sulphurContent: 60,
+      volatileMatter: 3200,
+      fixedCarbon: 3900,
+      coalGrade: { gradeC: {} },
+      sizeClassification: "0-50mm",
+    };
+
+    before(async () => {
+      await provider.connection.requestAirdrop(clerk.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+      await provider.connection.requestAirdrop(inspector.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+
+      [clerkRole] = PublicKey.findProgramAddressSync(
+        [Buffer.from("role"), quotaAccount1.toBuffer(), clerk.publicKey.toBuffer()],
+        program.programId
+      );
+      [inspectorRole] = PublicKey.findProgramAddressSync(
+        [Buffer.from("role"), quotaAccount1.toBuffer(), inspector.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await new Promise(resolve => setTimeout(resolve, 1000));
+    });
+
+    it("Lets the holder appoint a dispatch clerk who can use quota", async () => {
+      await program.methods
+        .grantRole({ dispatchClerk: {} })
+        .accounts({
+          quotaAccount: quotaAccount1,
+          roleAssignment: clerkRole,
+          member: clerk.publicKey,
+          authority: holder1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([holder1])
+        .rpc();
+
+      usageRecord = await nextUsageRecord(quotaAccount1);
+      await program.methods
+        .useQuota(new anchor.BN(200), shipmentId, qualityParams)
+        .accounts({
+          quotaAccount: quotaAccount1,
+          usageRecord,
+          roleAssignment: clerkRole,
+          delegate: null,
+          clearance: clearance1,
+          qualityPolicy,
+          authority: clerk.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([clerk])
+        .rpc();
+
+      const usageData = await program.account.usageRecord.fetch(usageRecord);
+      expect(usageData.holder.toString()).to.equal(holder1.publicKey.toString());
+      expect(usageData.amount.toString()).to.equal("200");
+    });
+
+    it("Prevents the holder from appointing inspectors", async () => {
+      try {
+        await program.methods
+          .grantRole({ inspector: {} })
+          .accounts({
+            quotaAccount: quotaAccount1,
+            roleAssignment: inspectorRole,
+            member: inspector.publicKey,
+            authority: holder1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([holder1])
+          .rpc();
+        expect.fail("Only the regulator may appoint inspectors");
+      } catch (error: any) {
+        expect(error.toString()).to.include("MissingPermission");
+      }
+    });
+
+    it("Lets a regulator-appointed inspector flag a shipment", async () => {
+      await program.methods
+        .grantRole({ inspector: {} })
+        .accounts({
+          quotaAccount: quotaAccount1,
+          roleAssignment: inspectorRole,
+          member: inspector.publicKey,
+          authority: regulator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([regulator])
+        .rpc();
+
+      await program.methods
+        .flagShipment("Declared GCV inconsistent with visual grade")
+        .accounts({
+          quotaAccount: quotaAccount1,
+          usageRecord,
+          roleAssignment: inspectorRole,
+          authority: inspector.publicKey,
+        })
+        .signers([inspector])
+        .rpc();
+
+      const usageData = await program.account.usageRecord.fetch(usageRecord);
+      expect(usageData.flaggedBy!.toString()).to.equal(inspector.publicKey.toString());
+    });
+
+    it("Returns a revoked role's rent to whoever paid for it", async () => {
+      try {
+        await program.methods
+          .revokeRole()
+          .accounts({
+            quotaAccount: quotaAccount1,
+            roleAssignment: clerkRole,
+            payer: regulator.publicKey,
+            authority: regulator.publicKey,
+          })
+          .signers([regulator])
+          .rpc();
+        expect.fail("rent should only go back to the original payer");
+      } catch (error: any) {
+        expect(error.toString()).to.include("PayerMismatch");
+      }
+    });
+
+    it("Revokes the dispatch clerk", async () => {
+      await program.methods
+        .revokeRole()
+        .accounts({
+          quotaAccount: quotaAccount1,
+          roleAssignment: clerkRole,
+          payer: holder1.publicKey,
+          authority: holder1.publicKey,
+        })
+        .signers([holder1])
+        .rpc();
+
+      const info = await provider.connection.getAccountInfo(clerkRole);
+      expect(info).to.be.null;
+    });
+  });
+
+  describe("Delegates", () => {
+    const weighbridgeKey = Keypair.generate();
+    let delegate: PublicKey;
+
+    const qualityParams = {
+      grossCalorificValue: 5400,
+      moistureContent: 1000,
+      ashContent: 1600,
+      sulphurContent: 55,
+      volatileMatter: 3300,
+      fixedCarbon: 4100,
+      coalGrade: { gradeB: {} },
+      sizeClassification: "0-50mm",
+    };
+
+    const dispatch = async (shipmentId: string, amount: number) =>
+      program.methods
+        .useQuota(new anchor.BN(amount), shipmentId, qualityParams)
+        .accounts({
+          quotaAccount: quotaAccount1,
+          usageRecord: await nextUsageRecord(quotaAccount1),
+          roleAssignment: null,
+          delegate,
+          clearance: clearance1,
+          qualityPolicy,
+          authority: weighbridgeKey.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([weighbridgeKey])
+        .rpc();
+
+    before(async () => {
+      await provider.connection.requestAirdrop(weighbridgeKey.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+      await new Promise(resolve => setTimeout(resolve, 1000));
+
+      [delegate] = PublicKey.findProgramAddressSync(
+        [Buffer.from("delegate"), quotaAccount1.toBuffer(), weighbridgeKey.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .createDelegate(
+          new anchor.BN(1000),
+          new anchor.BN(300),
+          new anchor.BN(Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60)
+        )
+        .accounts({
+          quotaAccount: quotaAccount1,
+          delegate,
+          delegateAuthority: weighbridgeKey.publicKey,
+          holder: holder1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([holder1])
+        .rpc();
+    });
+
+    it("Lets a delegate dispatch within its daily cap", async () => {
+      await dispatch("DLG001", 250);
+
+      const delegateData = await program.account.delegate.fetch(delegate);
+      expect(delegateData.usedTonnage.toString()).to.equal("250");
+      expect(delegateData.usedToday.toString()).to.equal("250");
+    });
+
+    it("Rejects dispatches beyond the daily cap", async () => {
+      try {
+        await dispatch("DLG002", 100);
+        expect.fail("use_quota should enforce the delegate's daily cap");
+      } catch (error: any) {
+        expect(error.toString()).to.include("DelegateDailyCapExceeded");
+      }
+    });
+
+    it("Restores the delegate's allowance when its shipment is cancelled", async () => {
+      const usageRecord = await nextUsageRecord(quotaAccount1);
+      await dispatch("DLG-CXL", 40);
+
+      await program.methods
+        .cancelShipment()
+        .accounts({
+          programConfig,
+          quotaAccount: quotaAccount1,
+          usageRecord,
+          clearance: clearance1,
+          roleAssignment: null,
+          delegate: null,
+          dispatchDelegate: delegate,
+          authority: holder1.publicKey,
+        })
+        .signers([holder1])
+        .rpc();
+
+      const delegateData = await program.account.delegate.fetch(delegate);
+      expect(delegateData.usedTonnage.toString()).to.equal("250");
+      expect(delegateData.usedToday.toString()).to.equal("250");
+    });
+
+    it("Stops accepting the delegate once revoked", async () => {
+      await program.methods
+        .revokeDelegate()
+        .accounts({
+          quotaAccount: quotaAccount1,
+          delegate,
+          holder: holder1.publicKey,
+        })
+        .signers([holder1])
+        .rpc();
+
+      try {
+        await dispatch("DLG003", 10);
+        expect.fail("use_quota should reject a revoked delegate");
+      } catch (error: any) {
+        expect(error.toString()).to.include("AccountNotInitialized");
+      }
+    });
+  });
+
+  describe("Utilization Thresholds", () => {
+    const setThresholds = (warningThresholds: number[], utilizationCap: number | null) =>
+      program.methods
+        .setUtilizationThresholds(Buffer.from(warningThresholds), utilizationCap)
+        .accounts({
+          quotaAccount: quotaAccount1,
+          regulator: regulator.publicKey,
+        })
+        .signers([regulator]);
+
+    after(async () => {
+      await setThresholds([80, 95], null).rpc();
+    });
+
+    it("Rejects thresholds out of order", async () => {
+      try {
+        await setThresholds([95, 80], null).rpc();
+        expect.fail("thresholds must be ascending");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidUtilizationThresholds");
+      }
+    });
+
+    it("Blocks dispatches past the regulator's cap", async () => {
+      const quotaData = await program.account.quotaAccount.fetch(quotaAccount1);
+      const utilization = quotaData.usedQuota.muln(100).div(quotaData.allocatedQuota).toNumber();
+      await setThresholds([80, 95], utilization + 1).rpc();
+
+      try {
+        await program.methods
+          .useQuota(quotaData.availableQuota, "SHIP-CAP", {
+            grossCalorificValue: 5500,
+            moistureContent: 1200,
+            ashContent: 1500,
+            sulphurContent: 50,
+            volatileMatter: 3500,
+            fixedCarbon: 5000,
+            coalGrade: { gradeB: {} },
+            sizeClassification: "0-50mm",
+          })
+          .accounts({
+            quotaAccount: quotaAccount1,
+            usageRecord: await nextUsageRecord(quotaAccount1),
+            roleAssignment: null,
+            delegate: null,
+            clearance: clearance1,
+            qualityPolicy,
+            authority: holder1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([holder1])
+          .rpc();
+        expect.fail("dispatch past the utilization cap should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("UtilizationThresholdExceeded");
+      }
+    });
+  });
+
+  describe("Dispatch Limits", () => {
+    const setLimits = (limits: any) =>
+      program.methods
+        .setDispatchLimits(limits)
+        .accounts({
+          quotaAccount: quotaAccount1,
+          regulator: regulator.publicKey,
+        })
+        .signers([regulator])
+        .rpc();
+
+    after(async () => {
+      await setLimits({ maxDailyTonnage: null, maxWeeklyTonnage: null, maxDailyShipments: null });
+    });
+
+    it("Only lets the regulator set limits", async () => {
+      try {
+        await program.methods
+          .setDispatchLimits({ maxDailyTonnage: new anchor.BN(1), maxWeeklyTonnage: null, maxDailyShipments: null })
+          .accounts({
+            quotaAccount: quotaAccount1,
+            regulator: holder1.publicKey,
+          })
+          .signers([holder1])
+          .rpc();
+        expect.fail("holder should not set their own dispatch limits");
+      } catch (error: any) {
+        expect(error.toString()).to.include("MissingPermission");
+      }
+    });
+
+    it("Rejects dispatches beyond the daily tonnage limit", async () => {
+      await setLimits({ maxDailyTonnage: new anchor.BN(1), maxWeeklyTonnage: null, maxDailyShipments: null });
+
+      try {
+        await program.methods
+          .useQuota(new anchor.BN(10), "SHIP-RATE", {
+            grossCalorificValue: 5500,
+            moistureContent: 1200,
+            ashContent: 1500,
+            sulphurContent: 50,
+            volatileMatter: 3500,
+            fixedCarbon: 5000,
+            coalGrade: { gradeB: {} },
+            sizeClassification: "0-50mm",
+          })
+          .accounts({
+            quotaAccount: quotaAccount1,
+            usageRecord: await nextUsageRecord(quotaAccount1),
+            roleAssignment: null,
+            delegate: null,
+            clearance: clearance1,
+            qualityPolicy,
+            authority: holder1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([holder1])
+          .rpc();
+        expect.fail("dispatch beyond the daily limit should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("DispatchRateLimitExceeded");
+      }
+    });
+  });
+
+  describe("Reservations", () => {
+    const amount = new anchor.BN(100);
+
+    const nextReservation = async () =>
+      sequencedPda("reservation", quotaAccount1, (await program.account.quotaAccount.fetch(quotaAccount1)).reservationCount);
+
+    const reserve = async (reservation: PublicKey, reference: string) =>
+      program.methods
+        .reserveQuota(amount, reference, new anchor.BN(Math.floor(Date.now() / 1000) + 60 * 60))
+        .accounts({
+          quotaAccount: quotaAccount1,
+          reservation,
+          roleAssignment: null,
+          authority: holder1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([holder1])
+        .rpc();
+
+    it("Holds tonnage until the reservation is released", async () => {
+      const before = await program.account.quotaAccount.fetch(quotaAccount1);
+      const reservation = await nextReservation();
+      await reserve(reservation, "GATE-2-TRUCK-17");
+
+      const held = await program.account.quotaAccount.fetch(quotaAccount1);
+      expect(held.reservedQuota.sub(before.reservedQuota).toString()).to.equal(amount.toString());
+      expect(before.availableQuota.sub(held.availableQuota).toString()).to.equal(amount.toString());
+
+      await program.methods
+        .releaseReservation()
+        .accounts({
+          quotaAccount: quotaAccount1,
+          reservation,
+          payer: holder1.publicKey,
+          roleAssignment: null,
+          authority: holder1.publicKey,
+        })
+        .signers([holder1])
+        .rpc();
+
+      const released = await program.account.quotaAccount.fetch(quotaAccount1);
+      expect(released.reservedQuota.toString()).to.equal(before.reservedQuota.toString());
+      expect(released.availableQuota.toString()).to.equal(before.availableQuota.toString());
+      expect(await provider.connection.getAccountInfo(reservation)).to.be.null;
+    });
+
+    it("Dispatches held tonnage by consuming the reservation", async () => {
+      const reservation = await nextReservation();
+      await reserve(reservation, "GATE-2-TRUCK-18");
+      const before = await program.account.quotaAccount.fetch(quotaAccount1);
+      const usageRecord = await nextUsageRecord(quotaAccount1);
+
+      await program.methods
+        .consumeReservation("SHIP-HELD", {
+          grossCalorificValue: 5500,
+          moistureContent: 1200,
+          ashContent: 1500,
+          sulphurContent: 50,
+          volatileMatter: 3500,
+          fixedCarbon: 5000,
+          coalGrade: { gradeB: {} },
+          sizeClassification: "0-50mm",
+        })
+        .accounts({
+          quotaAccount: quotaAccount1,
+          reservation,
+          payer: holder1.publicKey,
+          usageRecord,
+          roleAssignment: null,
+          clearance: clearance1,
+          qualityPolicy,
+          authority: holder1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([holder1])
+        .rpc();
+
+      const after = await program.account.quotaAccount.fetch(quotaAccount1);
+      expect(before.reservedQuota.sub(after.reservedQuota).toString()).to.equal(amount.toString());
+      expect(after.usedQuota.sub(before.usedQuota).toString()).to.equal(amount.toString());
+      expect(after.availableQuota.toString()).to.equal(before.availableQuota.toString());
+
+      const usageData = await program.account.usageRecord.fetch(usageRecord);
+      expect(usageData.shipmentId).to.equal("SHIP-HELD");
+      expect(usageData.amount.toString()).to.equal(amount.toString());
+    });
+
+    it("Keeps a quota active while tonnage is on hold", async () => {
+      // A quota of its own, since it ends up exhausted
+      const [quota] = PublicKey.findProgramAddressSync(
+        [Buffer.from("quota"), Buffer.from("MINE003"), holder1.publicKey.toBuffer()],
+        program.programId
+      );
+      const [clearance] = PublicKey.findProgramAddressSync(
+        [Buffer.from("clearance"), quota.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .initializeQuota("MINE003", new anchor.BN(300), validityPeriod, { annual: {} }, "Rajmahal Coalfield, Jharkhand", "")
+        .accounts({
+          quotaAccount: quota,
+          holder: holder1.publicKey,
+          regulator: regulator.publicKey,
+          regulatorRecord,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([regulator])
+        .rpc();
+
+      const reservation = sequencedPda("reservation", quota, new anchor.BN(0));
+      await program.methods
+        .reserveQuota(amount, "GATE-3-TRUCK-01", new anchor.BN(Math.floor(Date.now() / 1000) + 60 * 60))
+        .accounts({
+          quotaAccount: quota,
+          reservation,
+          roleAssignment: null,
+          authority: holder1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([holder1])
+        .rpc();
+
+      // Transfer away everything that is not on hold
+      const transferRecord = await nextTransferRecord(quota);
+      await program.methods
+        .requestTransfer(new anchor.BN(200), "Sale of unreserved tonnage", { commercial: {} })
+        .accounts({
+          fromQuota: quota,
+          toQuota: quotaAccount2,
+          transferRecord,
+          holder: holder1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([holder1])
+        .rpc();
+      await program.methods
+        .approveTransfer()
+        .accounts({ fromQuota: quota, transferRecord, regulator: regulator.publicKey })
+        .signers([regulator])
+        .rpc();
+      await program.methods
+        .acceptTransfer()
+        .accounts({ toQuota: quotaAccount2, transferRecord, holder: holder2.publicKey })
+        .signers([holder2])
+        .rpc();
+      await program.methods
+        .executeTransfer()
+        .accounts({ fromQuota: quota, toQuota: quotaAccount2, transferRecord, executor: holder1.publicKey })
+        .signers([holder1])
+        .rpc();
+
+      let quotaData = await program.account.quotaAccount.fetch(quota);
+      expect(quotaData.availableQuota.toString()).to.equal("0");
+      expect(quotaData.status).to.deep.equal({ active: {} });
+
+      await program.methods
+        .consumeReservation("SHIP-HELD-3", {
+          grossCalorificValue: 5500,
+          moistureContent: 1200,
+          ashContent: 1500,
+          sulphurContent: 50,
+          volatileMatter: 3500,
+          fixedCarbon: 5000,
+          coalGrade: { gradeB: {} },
+          sizeClassification: "0-50mm",
+        })
+        .accounts({
+          quotaAccount: quota,
+          reservation,
+          payer: holder1.publicKey,
+          usageRecord: sequencedPda("usage", quota, new anchor.BN(0)),
+          roleAssignment: null,
+          clearance,
+          qualityPolicy: null,
+          authority: holder1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([holder1])
+        .rpc();
+
+      quotaData = await program.account.quotaAccount.fetch(quota);
+      expect(quotaData.reservedQuota.toString()).to.equal("0");
+      expect(quotaData.status).to.deep.equal({ exhausted: {} });
+    });
+  });
+
+  describe("Batch Dispatch", () => {
+    const wagonParams = {
+      grossCalorificValue: 5500,
+      moistureContent: 1200,
+      ashContent: 1500,
+      sulphurContent: 50,
+      volatileMatter: 3500,
+      fixedCarbon: 5000,
+      coalGrade: { gradeB: {} },
+      sizeClassification: "0-50mm",
+    };
+
+    const useBatch = (shipments: any[], usageRecords: PublicKey[]) =>
+      program.methods
+        .useQuotaBatch(shipments)
+        .accounts({
+          quotaAccount: quotaAccount1,
+          roleAssignment: null,
+          clearance: clearance1,
+          qualityPolicy,
+          authority: holder1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          usageRecords.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }))
+        )
+        .signers([holder1])
+        .rpc();
+
+    it("Dispatches several shipments in one instruction", async () => {
+      const before = await program.account.quotaAccount.fetch(quotaAccount1);
+      const usageRecords = [0, 1].map((offset) =>
+        sequencedPda("usage", quotaAccount1, before.usageCount.addn(offset))
+      );
+
+      await useBatch(
+        [
+          { shipmentId: "RAKE1-W01", amount: new anchor.BN(60), qualityParams: wagonParams },
+          { shipmentId: "RAKE1-W02", amount: new anchor.BN(62), qualityParams: wagonParams },
+        ],
+        usageRecords
+      );
+
+      const after = await program.account.quotaAccount.fetch(quotaAccount1);
+      expect(after.usedQuota.sub(before.usedQuota).toString()).to.equal("122");
+      expect(after.usageCount.sub(before.usageCount).toString()).to.equal("2");
+
+      const second = await program.account.usageRecord.fetch(usageRecords[1]);
+      expect(second.shipmentId).to.equal("RAKE1-W02");
+      expect(second.sequence.toString()).to.equal(before.usageCount.addn(1).toString());
+    });
+
+    it("Requires a usage record account for every shipment", async () => {
+      const usageRecord = await nextUsageRecord(quotaAccount1);
+
+      try {
+        await useBatch(
+          [
+            { shipmentId: "RAKE2-W01", amount: new anchor.BN(60), qualityParams: wagonParams },
+            { shipmentId: "RAKE2-W02", amount: new anchor.BN(60), qualityParams: wagonParams },
+          ],
+          [usageRecord]
+        );
+        expect.fail("batch without a record per shipment should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("BatchAccountsMismatch");
+      }
+    });
+  });
+
+  describe("Rake Shipments", () => {
+    const wagons = [
+      { index: 0, wagonNumber: "BOXN-20571", tareWeightKg: new anchor.BN(22500), grossWeightKg: new anchor.BN(83000) },
+      { index: 1, wagonNumber: "BOXN-20588", tareWeightKg: new anchor.BN(22400), grossWeightKg: new anchor.BN(83500) },
+    ];
+    let rakeShipment: PublicKey;
+    let rakeUsageRecord: PublicKey;
+
+    // Leaves are sha256(0x00 || borsh(wagon)); nodes hash their sorted children after 0x01
+    const sha256 = (...parts: Buffer[]) => createHash("sha256").update(Buffer.concat(parts)).digest();
+    const leaf = (wagon: any) =>
+      sha256(Buffer.from([0]), program.coder.types.encode("wagonWeight", wagon));
+    const node = (a: Buffer, b: Buffer) =>
+      Buffer.compare(a, b) <= 0 ? sha256(Buffer.from([1]), a, b) : sha256(Buffer.from([1]), b, a);
+
+    const rakeParams = {
+      grossCalorificValue: 5500,
+      moistureContent: 1200,
+      ashContent: 1500,
+      sulphurContent: 50,
+      volatileMatter: 3500,
+      fixedCarbon: 5000,
+      coalGrade: { gradeB: {} },
+      sizeClassification: "0-50mm",
+    };
+
+    const dispatchRake = async (rakeNumber: string, netWeightKg: number) => {
+      rakeUsageRecord = await nextUsageRecord(quotaAccount1);
+      [rakeShipment] = PublicKey.findProgramAddressSync(
+        [Buffer.from("rake"), rakeUsageRecord.toBuffer()],
+        program.programId
+      );
+      const root = node(leaf(wagons[0]), leaf(wagons[1]));
+
+      await program.methods
+        .dispatchRake(rakeNumber, "BCCL-SDG3", 2, new anchor.BN(netWeightKg), Array.from(root), rakeParams)
+        .accounts({
+          quotaAccount: quotaAccount1,
+          usageRecord: rakeUsageRecord,
+          rakeShipment,
+          roleAssignment: null,
+          clearance: clearance1,
+          qualityPolicy,
+          authority: holder1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([holder1])
+        .rpc();
+    };
+
+    const verifyWagon = (wagon: any, proof: Buffer[], measured: number) =>
+      program.methods
+        .verifyRakeWagon(wagon, proof.map((hash) => Array.from(hash)), new anchor.BN(measured))
+        .accounts({
+          quotaAccount: quotaAccount1,
+          rakeShipment,
+          usageRecord: rakeUsageRecord,
+          roleAssignment: null,
+          inspector: regulator.publicKey,
+        })
+        .signers([regulator])
+        .rpc();
+
+    it("Charges the quota once for the whole rake", async () => {
+      const before = await program.account.quotaAccount.fetch(quotaAccount1);
+      await dispatchRake("RK-2024-0917", 121600);
+
+      const after = await program.account.quotaAccount.fetch(quotaAccount1);
+      expect(after.usedQuota.sub(before.usedQuota).toString()).to.equal("122");
+      const rakeData = await program.account.rakeShipment.fetch(rakeShipment);
+      expect(rakeData.wagonCount).to.equal(2);
+      expect(rakeData.usageRecord.toString()).to.equal(rakeUsageRecord.toString());
+    });
+
+    it("Lets an inspector verify a wagon against the rake's root", async () => {
+      await verifyWagon(wagons[0], [leaf(wagons[1])], 83200);
+
+      const rakeData = await program.account.rakeShipment.fetch(rakeShipment);
+      expect(rakeData.verifiedCount).to.equal(1);
+      expect(rakeData.verifiedNetWeightKg.toString()).to.equal("60500");
+      expect(rakeData.discrepancies).to.equal(0);
+      expect((await program.account.usageRecord.fetch(rakeUsageRecord)).flaggedBy).to.be.null;
+    });
+
+    it("Rejects wagon weights that were not committed to", async () => {
+      const tampered = { ...wagons[1], grossWeightKg: new anchor.BN(80000) };
+
+      try {
+        await verifyWagon(tampered, [leaf(wagons[0])], 80000);
+        expect.fail("wagon outside the Merkle root should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidWagonProof");
+      }
+    });
+
+    it("Flags a rake whose wagons outweigh its declared net weight", async () => {
+      // 60 t declared, but the first wagon alone carries 60.5 t
+      await dispatchRake("RK-2024-0918", 60000);
+      await verifyWagon(wagons[0], [leaf(wagons[1])], 83000);
+
+      const usageData = await program.account.usageRecord.fetch(rakeUsageRecord);
+      expect(usageData.flaggedBy.toString()).to.equal(regulator.publicKey.toString());
+      expect(usageData.flagReason).to.include("RK-2024-0918");
+    });
+
+    it("Flags a rake whose wagon re-weighs outside the tolerance", async () => {
+      // Declared at 83 t gross, re-weighed at 85 t
+      await dispatchRake("RK-2024-0921", 121600);
+      await verifyWagon(wagons[0], [leaf(wagons[1])], 85000);
+
+      const rakeData = await program.account.rakeShipment.fetch(rakeShipment);
+      expect(rakeData.discrepancies).to.equal(1);
+      expect(rakeData.measuredNetWeightKg.toString()).to.equal("62500");
+      const usageData = await program.account.usageRecord.fetch(rakeUsageRecord);
+      expect(usageData.flaggedBy.toString()).to.equal(regulator.publicKey.toString());
+      expect(usageData.flagReason).to.include("re-weighed");
+    });
+
+    it("Refuses to verify the wagons of a cancelled rake", async () => {
+      await dispatchRake("RK-2024-0922", 121600);
+      await program.methods
+        .cancelShipment()
+        .accounts({
+          programConfig,
+          quotaAccount: quotaAccount1,
+          usageRecord: rakeUsageRecord,
+          clearance: clearance1,
+          roleAssignment: null,
+          delegate: null,
+          dispatchDelegate: null,
+          authority: holder1.publicKey,
+        })
+        .signers([holder1])
+        .rpc();
+
+      try {
+        await verifyWagon(wagons[0], [leaf(wagons[1])], 83000);
+        expect.fail("verify_rake_wagon should refuse a cancelled rake");
+      } catch (error: any) {
+        expect(error.toString()).to.include("ShipmentWithdrawn");
+      }
+    });
+
+    it("Keeps a rake until its usage record is closed", async () => {
+      try {
+        await program.methods
+          .closeRakeShipment()
+          .accounts({
+            usageRecord: rakeUsageRecord,
+            rakeShipment,
+            payer: holder1.publicKey,
+            authority: holder1.publicKey,
+          })
+          .signers([holder1])
+          .rpc();
+        expect.fail("close_rake_shipment should wait for the usage record to close");
+      } catch (error: any) {
+        expect(error.toString()).to.include("RecordNotClosable");
+      }
+    });
+  });
+
+  describe("Lab Assays", () => {
+    const lab = Keypair.generate();
+    const sampleId = "NABL-S-0001";
+    const usageRecord = () => sequencedPda("usage", quotaAccount1, new anchor.BN(0));
+    let labRecord: PublicKey;
+
+    // Borsh encoding of AssayPayload, the message the lab signs
+    const assayPayload = (record: PublicKey, params: any) => {
+      const sample = Buffer.from(sampleId);
+      const sampleLen = Buffer.alloc(4);
+      sampleLen.writeUInt32LE(sample.length);
+      return Buffer.concat([
+        record.toBuffer(),
+        sampleLen,
+        sample,
+        program.coder.types.encode("qualityParameters", params),
+      ]);
+    };
+
+    const attachAssay = (params: any) =>
+      program.methods.attachAssay(sampleId, params).accounts({
+        quotaAccount: quotaAccount1,
+        usageRecord: usageRecord(),
+        labRecord,
+        roleAssignment: null,
+        authority: holder1.publicKey,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      }).signers([holder1]);
+
+    before(async () => {
+      [labRecord] = PublicKey.findProgramAddressSync(
+        [Buffer.from("lab"), lab.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .registerLab("CIMFR Dhanbad", "NABL-TC-1234")
+        .accounts({
+          programConfig,
+          labRecord,
+          lab: lab.publicKey,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+    });
+
+    it("Rejects an assay without the lab's signature", async () => {
+      const usageData = await program.account.usageRecord.fetch(usageRecord());
+
+      try {
+        await attachAssay(usageData.qualityParams).rpc();
+        expect.fail("assay without an Ed25519 instruction should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidAssaySignature");
+      }
+    });
+
+    it("Attaches a lab-signed assay to a shipment", async () => {
+      const usageData = await program.account.usageRecord.fetch(usageRecord());
+      const params = usageData.qualityParams;
+
+      await attachAssay(params)
+        .preInstructions([
+          Ed25519Program.createInstructionWithPrivateKey({
+            privateKey: lab.secretKey,
+            message: assayPayload(usageRecord(), params),
+          }),
+        ])
+        .rpc();
+
+      const assayed = await program.account.usageRecord.fetch(usageRecord());
+      expect(assayed.assayLab.toString()).to.equal(lab.publicKey.toString());
+      expect(assayed.assaySampleId).to.equal(sampleId);
+      expect(assayed.assayedParams.grossCalorificValue).to.equal(params.grossCalorificValue);
+    });
+
+    it("Accepts only one assay per shipment", async () => {
+      const params = (await program.account.usageRecord.fetch(usageRecord())).qualityParams;
+
+      try {
+        await attachAssay(params)
+          .preInstructions([
+            Ed25519Program.createInstructionWithPrivateKey({
+              privateKey: lab.secretKey,
+              message: assayPayload(usageRecord(), params),
+            }),
+          ])
+          .rpc();
+        expect.fail("second assay should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("AssayAlreadyAttached");
+      }
+    });
+  });
+
+  describe("Grade Sub-quotas", () => {
+    // Dispatched before the split, cancelled after it
+    let preSplitRecord: PublicKey;
+    let clearance2: PublicKey;
+
+    before(async () => {
+      [clearance2] = PublicKey.findProgramAddressSync(
+        [Buffer.from("clearance"), quotaAccount2.toBuffer()],
+        program.programId
+      );
+      preSplitRecord = await nextUsageRecord(quotaAccount2);
+      await program.methods
+        .useQuota(new anchor.BN(100), "SHIP-PRESPLIT", {
+          grossCalorificValue: 5500,
+          moistureContent: 1000,
+          ashContent: 1800,
+          sulphurContent: 60,
+          volatileMatter: 3000,
+          fixedCarbon: 5200,
+          coalGrade: { gradeB: {} },
+          sizeClassification: "0-50mm",
+        })
+        .accounts({
+          quotaAccount: quotaAccount2,
+          usageRecord: preSplitRecord,
+          roleAssignment: null,
+          delegate: null,
+          clearance: clearance2,
+          qualityPolicy: null,
+          authority: holder2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([holder2])
+        .rpc();
+    });
+
+    it("Splits the concession's tonnage by coal grade", async () => {
+      await program.methods
+        .setGradeAllocation({ gradeB: {} }, new anchor.BN(6000))
+        .accounts({
+          quotaAccount: quotaAccount2,
+          regulator: regulator.publicKey,
+        })
+        .signers([regulator])
+        .rpc();
+
+      const quotaData = await program.account.quotaAccount.fetch(quotaAccount2);
+      expect(quotaData.gradeAllocations).to.have.length(1);
+      expect(quotaData.gradeAllocations[0].grade).to.deep.equal({ gradeB: {} });
+      expect(quotaData.gradeAllocations[0].available.toString()).to.equal("6000");
+    });
+
+    it("Does not credit a grade with tonnage dispatched before the split", async () => {
+      await program.methods
+        .cancelShipment()
+        .accounts({
+          programConfig,
+          quotaAccount: quotaAccount2,
+          usageRecord: preSplitRecord,
+          clearance: clearance2,
+          roleAssignment: null,
+          delegate: null,
+          dispatchDelegate: null,
+          authority: holder2.publicKey,
+        })
+        .signers([holder2])
+        .rpc();
+
+      const quotaData = await program.account.quotaAccount.fetch(quotaAccount2);
+      expect(quotaData.gradeAllocations[0].used.toString()).to.equal("0");
+      expect(quotaData.gradeAllocations[0].available.toString()).to.equal("6000");
+    });
+
+    it("Keeps grade allocations within the concession's total", async () => {
+      const quotaData = await program.account.quotaAccount.fetch(quotaAccount2);
+
+      try {
+        await program.methods
+          .setGradeAllocation({ gradeC: {} }, quotaData.allocatedQuota.sub(new anchor.BN(5000)))
+          .accounts({
+            quotaAccount: quotaAccount2,
+            regulator: regulator.publicKey,
+          })
+          .signers([regulator])
+          .rpc();
+        expect.fail("grade allocations should not exceed allocated_quota");
+      } catch (error: any) {
+        expect(error.toString()).to.include("GradeAllocationExceedsQuota");
+      }
+    });
+
+    it("Refuses to cut the allocation below the grade sub-allocations", async () => {
+      try {
+        await program.methods
+          .updateQuota(new anchor.BN(5000), null, null, "Mid-year cut")
+          .accounts({
+            quotaAccount: quotaAccount2,
+            regulator: regulator.publicKey,
+          })
+          .signers([regulator])
+          .rpc();
+        expect.fail("allocated_quota should not drop below the 6000 t sanctioned for grade B");
+      } catch (error: any) {
+        expect(error.toString()).to.include("GradeAllocationExceedsQuota");
+      }
+    });
+  });
+
+  describe("Revoke Quota", () => {
+    // Held before revocation, released after it
+    let reservation: PublicKey;
+    // Requested before revocation, cancelled after it
+    let transferRecord: PublicKey;
+    let closedQuota: any;
+    // Appointed before revocation, closed after it
+    const clerk = Keypair.generate();
+    let clerkRole: PublicKey;
+
+    const closeClerkRole = () =>
+      program.methods
+        .closeRoleAssignment()
+        .accounts({
+          quotaAccount: quotaAccount2,
+          roleAssignment: clerkRole,
+          payer: holder2.publicKey,
+          authority: regulator.publicKey,
+        })
+        .signers([regulator]);
+
+    before(async () => {
+      reservation = sequencedPda(
+        "reservation",
+        quotaAccount2,
+        (await program.account.quotaAccount.fetch(quotaAccount2)).reservationCount
+      );
+      await program.methods
+        .reserveQuota(new anchor.BN(100), "GATE-1-TRUCK-04", new anchor.BN(Math.floor(Date.now() / 1000) + 60 * 60))
+        .accounts({
+          quotaAccount: quotaAccount2,
+          reservation,
+          roleAssignment: null,
+          authority: holder2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([holder2])
+        .rpc();
+
+      transferRecord = await nextTransferRecord(quotaAccount2);
+      await program.methods
+        .requestTransfer(new anchor.BN(50), "Sale to MINE001", { commercial: {} })
+        .accounts({
+          fromQuota: quotaAccount2,
+          toQuota: quotaAccount1,
+          transferRecord,
+          holder: holder2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([holder2])
+        .rpc();
+
+      [clerkRole] = PublicKey.findProgramAddressSync(
+        [Buffer.from("role"), quotaAccount2.toBuffer(), clerk.publicKey.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .grantRole({ dispatchClerk: {} })
+        .accounts({
+          quotaAccount: quotaAccount2,
+          roleAssignment: clerkRole,
+          member: clerk.publicKey,
+          authority: holder2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([holder2])
+        .rpc();
+    });
+
+    it("Keeps an active quota's role assignments open", async () => {
+      try {
+        await closeClerkRole().rpc();
+        expect.fail("close_role_assignment should wait for the quota to be revoked or closed");
+      } catch (error: any) {
+        expect(error.toString()).to.include("QuotaNotRetired");
+      }
+    });
+
+    // Runs last: revocation is terminal for quotaAccount2
+    it("Revokes a quota with a reason code and records the revoker", async () => {
+      await program.methods
+        .revokeQuota({ environmentalViolation: {} }, "Dumping overburden outside the approved area")
+        .accounts({
+          quotaAccount: quotaAccount2,
+          regulator: regulator.publicKey,
+        })
+        .signers([regulator])
+        .rpc();
+
+      const quotaData = await program.account.quotaAccount.fetch(quotaAccount2);
+      expect(quotaData.status).to.deep.equal({ revoked: {} });
+      expect(quotaData.revokedBy.toString()).to.equal(regulator.publicKey.toString());
+      expect(quotaData.revocationReason).to.deep.equal({ environmentalViolation: {} });
+    });
+
+    it("Does not allow a revoked quota to be reactivated", async () => {
+      try {
+        await program.methods
+          .reactivateQuota()
+          .accounts({
+            quotaAccount: quotaAccount2,
+            regulator: regulator.publicKey,
+          })
+          .signers([regulator])
+          .rpc();
+        expect.fail("reactivate_quota should refuse a revoked quota");
+      } catch (error: any) {
+        expect(error.toString()).to.include("QuotaRevoked");
+      }
+    });
+
+    it("Returns a revoked quota's role rent to whoever paid it", async () => {
+      const balanceBefore = await provider.connection.getBalance(holder2.publicKey);
+
+      await closeClerkRole().rpc();
+
+      const balanceAfter = await provider.connection.getBalance(holder2.publicKey);
+      expect(balanceAfter).to.be.greaterThan(balanceBefore);
+      expect(await provider.connection.getAccountInfo(clerkRole)).to.be.null;
+    });
+
+    it("Keeps the revoked quota frozen when a hold is released", async () => {
+      const before = await program.account.quotaAccount.fetch(quotaAccount2);
+
+      await program.methods
+        .releaseReservation()
+        .accounts({
+          quotaAccount: quotaAccount2,
+          reservation,
+          payer: holder2.publicKey,
+          roleAssignment: null,
+          authority: holder2.publicKey,
+        })
+        .signers([holder2])
+        .rpc();
+
+      const after = await program.account.quotaAccount.fetch(quotaAccount2);
+      expect(after.reservedQuota.toString()).to.equal("0");
+      expect(after.availableQuota.toString()).to.equal(before.availableQuota.toString());
+      expect(after.status).to.deep.equal({ revoked: {} });
+    });
+
+    it("Refuses to close a quota with a transfer still in flight", async () => {
+      try {
+        await program.methods
+          .closeQuotaAccount()
+          .accounts({
+            quotaAccount: quotaAccount2,
+            regulator: regulator.publicKey,
+          })
+          .signers([regulator])
+          .rpc();
+        expect.fail("close_quota_account should wait for open transfers to settle");
+      } catch (error: any) {
+        expect(error.toString()).to.include("QuotaNotClosable");
+      }
+    });
+
+    it("Lets the regulator cancel a revoked quota's transfer", async () => {
+      await program.methods
+        .cancelTransfer()
+        .accounts({
+          fromQuota: quotaAccount2,
+          transferRecord,
+          authority: regulator.publicKey,
+        })
+        .signers([regulator])
+        .rpc();
+
+      const transferData = await program.account.transferRecord.fetch(transferRecord);
+      expect(transferData.status).to.deep.equal({ cancelled: {} });
+      const quotaData = await program.account.quotaAccount.fetch(quotaAccount2);
+      expect(quotaData.openTransfers.toString()).to.equal("0");
+    });
+
+    it("Closes the revoked quota and returns its rent to the regulator", async () => {
+      closedQuota = await program.account.quotaAccount.fetch(quotaAccount2);
+      const balanceBefore = await provider.connection.getBalance(regulator.publicKey);
+
+      await program.methods
+        .closeQuotaAccount()
+        .accounts({
+          quotaAccount: quotaAccount2,
+          regulator: regulator.publicKey,
+        })
+        .signers([regulator])
+        .rpc();
+
+      const balanceAfter = await provider.connection.getBalance(regulator.publicKey);
+      expect(balanceAfter).to.be.greaterThan(balanceBefore);
+      expect(await provider.connection.getAccountInfo(quotaAccount2)).to.be.null;
+    });
+
+    it("Re-issues a closed quota without reusing its record addresses", async () => {
+      await program.methods
+        .initializeQuota(concessionId2, allocatedQuota, validityPeriod, { annual: {} }, "Talcher Coalfield, Odisha", "")
+        .accounts({
+          quotaAccount: quotaAccount2,
+          holder: holder2.publicKey,
+          regulator: regulator.publicKey,
+          regulatorRecord,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([regulator])
+        .rpc();
+
+      const quotaData = await program.account.quotaAccount.fetch(quotaAccount2);
+      expect(quotaData.status).to.deep.equal({ active: {} });
+      expect(quotaData.usageCount.toString()).to.equal(closedQuota.usageCount.toString());
+      expect(quotaData.transferCount.toString()).to.equal(closedQuota.transferCount.toString());
+      expect(quotaData.reservationCount.toString()).to.equal(closedQuota.reservationCount.toString());
+    });
+  });
 });